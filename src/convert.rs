@@ -1,58 +1,316 @@
-use std::collections::HashMap;
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::iter::Peekable;
 
-use crate::font::{Font, FontMap};
+use crate::decoration::is_combining;
+use crate::fallback::Fallback;
+use crate::font::Font;
 
-/// This struct holds each font's mapping between normal characters to ones of the font.
-pub struct Converter {
-    font_mappings: HashMap<Font, FontMap>,
+/// This struct converts characters between normal ones and ones of each font.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Converter;
+
+/// A part of input which is decorated with the same font.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedSpan {
+    /// Fonts which can produce this span. Empty if the span is not decorated.
+    pub fonts: Vec<Font>,
+    pub text: String,
 }
 
 impl Converter {
-    pub fn new(fonts: &[Font]) -> Self {
-        let mut font_mappings = HashMap::new();
-        for font in fonts {
-            font_mappings.insert(*font, font.characters());
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get the normal character corresponding to given character decorated with any font.
+    fn revert_char(c: char) -> Option<char> {
+        Self::revert_char_in(c, Font::ALL.iter().copied())
+    }
+
+    fn revert_char_in(c: char, mut fonts: impl Iterator<Item = Font>) -> Option<char> {
+        // No font uses ASCII characters, so skip searching for most of input.
+        if c.is_ascii() {
+            return None;
         }
+        fonts.find_map(|font| font.characters().revert(c))
+    }
 
-        Self { font_mappings }
+    /// Get the normal character corresponding to given alphanumeric character decorated with any
+    /// font which is restyled (see `Font::is_restyled`). Other characters are returned as they
+    /// are.
+    pub(crate) fn normalize_char(c: char) -> char {
+        // Fullwidth punctuation and ideographic space are common in Japanese text, so only
+        // alphanumeric characters are normalized.
+        let fonts = Font::ALL.iter().copied().filter(Font::is_restyled);
+        Self::revert_char_in(c, fonts)
+            .filter(|reverted| reverted.is_alphanumeric())
+            .unwrap_or(c)
     }
 
     /// Convert given characters to specified font.
+    /// Characters already decorated with another font are converted as well, unless the font is
+    /// not restyled (see `Font::is_restyled`).
     /// Non-alphanumeric characters remain unchanged.
+    #[cfg(feature = "alloc")]
     pub fn convert(&self, source: &[char], font: Font) -> String {
-        let mapping = self
-            .font_mappings
-            .get(&font)
-            .expect("Unexpected font specified");
-        let converted = source.iter().map(|original| {
-            if let Some(converted) = mapping.get(original) {
-                converted
+        self.convert_chars(source.iter().copied(), font).collect()
+    }
+
+    /// Same as `Converter::convert`, but takes a string slice.
+    #[cfg(feature = "alloc")]
+    pub fn convert_str(&self, source: &str, font: Font) -> String {
+        self.convert_chars(source.chars(), font).collect()
+    }
+
+    /// Same as `Converter::convert`, but lazily yields converted characters without allocation.
+    pub fn convert_chars<I>(&self, source: I, font: Font) -> ConvertChars<I::IntoIter>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.convert_chars_with(source, font, Fallback::Keep)
+    }
+
+    /// Same as `Converter::convert_chars`, but handles characters which the font does not have
+    /// according to the fallback policy.
+    pub fn convert_chars_with<I>(
+        &self,
+        source: I,
+        font: Font,
+        fallback: Fallback,
+    ) -> ConvertChars<I::IntoIter>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        ConvertChars {
+            source: Normalize::new(source, Converter::normalize_char),
+            font,
+            fallback,
+            previous: None,
+            pending: None,
+        }
+    }
+
+    /// Decompose accented characters into base characters and combining marks (NFD), e.g. 'é' into
+    /// 'e' and U+0301, so that converting the result to specified font decorates the base
+    /// characters. Characters whose base characters do not belong to the font remain unchanged.
+    #[cfg(feature = "decompose")]
+    pub fn decompose(&self, source: &[char], font: Font) -> String {
+        self.decompose_chars(source.iter().copied(), font)
+    }
+
+    /// Same as `Converter::decompose`, but takes a string slice.
+    #[cfg(feature = "decompose")]
+    pub fn decompose_str(&self, source: &str, font: Font) -> String {
+        self.decompose_chars(source.chars(), font)
+    }
+
+    #[cfg(feature = "decompose")]
+    fn decompose_chars(&self, source: impl Iterator<Item = char>, font: Font) -> String {
+        let mut decomposed = String::new();
+        for c in source {
+            let start = decomposed.len();
+            unicode_normalization::char::decompose_canonical(c, |d| decomposed.push(d));
+            let base = decomposed[start..].chars().next();
+            if base.and_then(|base| font.characters().get(base)).is_none() {
+                decomposed.truncate(start);
+                decomposed.push(c);
+            }
+        }
+        decomposed
+    }
+
+    /// Convert characters decorated with any font back to normal ones.
+    /// Characters which do not belong to any font remain unchanged, and U+200C inserted for
+    /// `Font::Emoji` are removed.
+    #[cfg(feature = "alloc")]
+    pub fn revert(&self, source: &[char]) -> String {
+        self.revert_chars(source.iter().copied()).collect()
+    }
+
+    /// Same as `Converter::revert`, but takes a string slice.
+    #[cfg(feature = "alloc")]
+    pub fn revert_str(&self, source: &str) -> String {
+        self.revert_chars(source.chars()).collect()
+    }
+
+    /// Same as `Converter::revert`, but lazily yields reverted characters without allocation.
+    pub fn revert_chars<I>(&self, source: I) -> impl Iterator<Item = char>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        Normalize::new(source, |c| Self::revert_char(c).unwrap_or(c))
+    }
+
+    /// Split given characters into spans by the font they are decorated with.
+    /// Latin alphabets not decorated form spans with no font, and other characters such as
+    /// numbers, spaces and symbols, including fullwidth ones, belong to the adjacent span.
+    #[cfg(feature = "alloc")]
+    pub fn detect(&self, source: &[char]) -> Vec<DetectedSpan> {
+        // `None` means that the span consists only of characters which do not tell any font.
+        let mut spans: Vec<(Option<Vec<Font>>, String)> = Vec::new();
+        for &c in source {
+            let fonts = Font::ALL
+                .iter()
+                .copied()
+                .filter(|font| {
+                    font.characters()
+                        .revert(c)
+                        .is_some_and(|reverted| reverted.is_alphanumeric())
+                })
+                .collect::<Vec<_>>();
+            let candidates = if !fonts.is_empty() {
+                Some(fonts.as_slice())
+            } else if c.is_ascii_alphabetic() {
+                Some([].as_slice())
             } else {
-                original
+                None
+            };
+
+            match (spans.last_mut(), candidates) {
+                (Some((_, text)), None) => text.push(c),
+                (Some((fonts @ None, text)), Some(candidates)) => {
+                    *fonts = Some(candidates.to_vec());
+                    text.push(c);
+                }
+                (Some((Some(fonts), text)), Some(candidates))
+                    if fonts.iter().any(|font| candidates.contains(font)) =>
+                {
+                    fonts.retain(|font| candidates.contains(font));
+                    text.push(c);
+                }
+                (Some((Some(fonts), text)), Some([])) if fonts.is_empty() => text.push(c),
+                (_, candidates) => spans.push((candidates.map(<[Font]>::to_vec), c.to_string())),
+            }
+        }
+
+        spans
+            .into_iter()
+            .map(|(fonts, text)| DetectedSpan {
+                fonts: fonts.unwrap_or_default(),
+                text,
+            })
+            .collect()
+    }
+
+    /// Same as `Converter::detect`, but takes a string slice.
+    #[cfg(feature = "alloc")]
+    pub fn detect_str(&self, source: &str) -> Vec<DetectedSpan> {
+        self.detect(&source.chars().collect::<Vec<_>>())
+    }
+}
+
+/// Whether the character is a regional indicator symbol, which `Font::Emoji` converts letters to.
+pub(crate) fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Iterator which converts decorated characters to normal ones with `revert`, removing U+200C
+/// inserted for `Font::Emoji`. Pairs of regional indicator symbols are flags rather than text
+/// decorated with `Font::Emoji`, so they are kept as they are.
+#[derive(Debug, Clone)]
+pub(crate) struct Normalize<I: Iterator<Item = char>> {
+    source: Peekable<I>,
+    revert: fn(char) -> char,
+    previous: Option<char>,
+}
+
+impl<I: Iterator<Item = char>> Normalize<I> {
+    pub(crate) fn new(source: impl IntoIterator<IntoIter = I>, revert: fn(char) -> char) -> Self {
+        Self {
+            source: source.into_iter().peekable(),
+            revert,
+            previous: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Normalize<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let c = self.source.next()?;
+            let previous = self.previous.replace(c);
+            // `Font::Emoji` inserts U+200C next to regional indicator symbols only, so other ones,
+            // e.g. in Persian text, are kept.
+            if c == '\u{200C}'
+                && (previous.is_some_and(is_regional_indicator)
+                    || self
+                        .source
+                        .peek()
+                        .is_some_and(|&c| is_regional_indicator(c)))
+            {
+                continue;
+            }
+            if is_regional_indicator(c)
+                && (previous.is_some_and(is_regional_indicator)
+                    || self
+                        .source
+                        .peek()
+                        .is_some_and(|&c| is_regional_indicator(c)))
+            {
+                return Some(c);
             }
-        });
+            return Some((self.revert)(c));
+        }
+    }
+}
+
+/// Iterator returned by `Converter::convert_chars`.
+#[derive(Debug, Clone)]
+pub struct ConvertChars<I: Iterator<Item = char>> {
+    source: Normalize<I>,
+    font: Font,
+    fallback: Fallback,
+    /// Last normal character, or `None` at the beginning.
+    previous: Option<char>,
+    /// Character to yield after U+200C.
+    pending: Option<char>,
+}
+
+impl<I: Iterator<Item = char>> Iterator for ConvertChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(c) = self.pending.take() {
+            return Some(c);
+        }
 
-        if font == Font::Emoji {
+        let (original, converted) = loop {
+            let original = self.source.next()?;
+            if let Some(converted) = self.fallback.convert_char(original, self.font) {
+                break (original, converted);
+            }
+        };
+
+        let previous = self.previous.replace(original);
+        // Regional indicator symbols are left normal only in flags, which should not be split.
+        let is_flag =
+            previous.is_some_and(is_regional_indicator) && is_regional_indicator(original);
+        if self.font == Font::Emoji && previous.is_some() && !is_flag && !is_combining(&converted) {
             // In this application, we want reginal indicator symbols to be rendered as emoji.
             // To prevent them from being rendered as flags, we insert zero-width joiner(U+200C) between each character.
             // For a simple implementation, we U+200C between all characters.
-            converted
-                .map(|c| c.to_string())
-                .collect::<Vec<_>>()
-                .join('\u{200C}'.to_string().as_str())
+            // Combining marks are kept next to the characters they belong to.
+            self.pending = Some(converted);
+            Some('\u{200C}')
         } else {
-            converted.collect()
+            Some(converted)
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
-    use clap::ValueEnum;
 
     fn setup_converter() -> Converter {
-        Converter::new(Font::value_variants())
+        Converter::new()
     }
 
     #[test]
@@ -234,4 +492,311 @@ mod tests {
             converter.convert(&source, Font::Emoji)
         );
     }
+
+    #[test]
+    fn circled() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "⑧ⓌⓨⓜⓍⓑⓁⓋ③ⓝⒾⓃⓊⓗⓄⓞⓆⓚⓀⒼⓕⓤⓎ⑨ⒽⓢⓏⓈⒸ⑥⑦⑤ⓙⓩⒷⒺⓣⒶⓉⒹⒻⓂⓇⓖⓅⓟⓔⓐⓧⓘⒿⓒⓡ⓪ⓠ④ⓛ①ⓦ②ⓓⓥ",
+            converter.convert(&source, Font::Circled)
+        );
+    }
+
+    #[test]
+    fn negative_circled() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "❽🅦🅨🅜🅧🅑🅛🅥❸🅝🅘🅝🅤🅗🅞🅞🅠🅚🅚🅖🅕🅤🅨❾🅗🅢🅩🅢🅒❻❼❺🅙🅩🅑🅔🅣🅐🅣🅓🅕🅜🅡🅖🅟🅟🅔🅐🅧🅘🅙🅒🅡⓿🅠❹🅛❶🅦❷🅓🅥",
+            converter.convert(&source, Font::NegativeCircled)
+        );
+    }
+
+    #[test]
+    fn squared() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "8🅆🅈🄼🅇🄱🄻🅅3🄽🄸🄽🅄🄷🄾🄾🅀🄺🄺🄶🄵🅄🅈9🄷🅂🅉🅂🄲675🄹🅉🄱🄴🅃🄰🅃🄳🄵🄼🅁🄶🄿🄿🄴🄰🅇🄸🄹🄲🅁0🅀4🄻1🅆2🄳🅅",
+            converter.convert(&source, Font::Squared)
+        );
+    }
+
+    #[test]
+    fn negative_squared() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "8🆆🆈🅼🆇🅱🅻🆅3🅽🅸🅽🆄🅷🅾🅾🆀🅺🅺🅶🅵🆄🆈9🅷🆂🆉🆂🅲675🅹🆉🅱🅴🆃🅰🆃🅳🅵🅼🆁🅶🅿🅿🅴🅰🆇🅸🅹🅲🆁0🆀4🅻1🆆2🅳🆅",
+            converter.convert(&source, Font::NegativeSquared)
+        );
+    }
+
+    #[test]
+    fn parenthesized() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "⑻🄦⒴⒨🄧⒝🄛🄥⑶⒩🄘🄝🄤⒣🄞⒪🄠⒦🄚🄖⒡⒰🄨⑼🄗⒮🄩🄢🄒⑹⑺⑸⒥⒵🄑🄔⒯🄐🄣🄓🄕🄜🄡⒢🄟⒫⒠⒜⒳⒤🄙⒞⒭0⒬⑷⒧⑴⒲⑵⒟⒱",
+            converter.convert(&source, Font::Parenthesized)
+        );
+    }
+
+    #[test]
+    fn full_stop() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "⒏WymXbLV⒊nINUhOoQkKGfuY⒐HsZSC⒍⒎⒌jzBEtATDFMRgPpeaxiJcr🄀q⒋l⒈w⒉dv",
+            converter.convert(&source, Font::FullStop)
+        );
+    }
+
+    #[test]
+    fn fullwidth() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "８ＷｙｍＸｂＬＶ３ｎＩＮＵｈＯｏＱｋＫＧｆｕＹ９ＨｓＺＳＣ６７５ｊｚＢＥｔＡＴＤＦＭＲｇＰｐｅａｘｉＪｃｒ０ｑ４ｌ１ｗ２ｄｖ",
+            converter.convert(&source, Font::Fullwidth)
+        );
+        assert_eq!(
+            "Ｈｅｌｌｏ，\u{3000}ｗｏｒｌｄ！",
+            converter.convert_str("Hello, world!", Font::Fullwidth)
+        );
+    }
+
+    #[test]
+    fn small_caps() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "8WʏᴍXʙLV3ɴINUʜOᴏQᴋKGꜰᴜY9HꜱZSC675ᴊᴢBEᴛATDFMRɢPᴘᴇᴀxɪJᴄʀ0ꞯ4ʟ1ᴡ2ᴅᴠ",
+            converter.convert(&source, Font::SmallCaps)
+        );
+    }
+
+    #[test]
+    fn superscript() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "⁸ᵂʸᵐˣᵇᴸⱽ³ⁿᴵᴺᵁʰᴼᵒꟴᵏᴷᴳᶠᵘʸ⁹ᴴˢᶻˢᶜ⁶⁷⁵ʲᶻᴮᴱᵗᴬᵀᴰᶠᴹᴿᵍᴾᵖᵉᵃˣⁱᴶᶜʳ⁰q⁴ˡ¹ʷ²ᵈᵛ",
+            converter.convert(&source, Font::Superscript)
+        );
+        assert_eq!("ˣ⁽ⁿ⁺¹⁾", converter.convert_str("x(n+1)", Font::Superscript));
+    }
+
+    #[test]
+    fn subscript() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            "₈Wyₘₓbₗᵥ₃ₙᵢₙᵤₕₒₒQₖₖGfᵤY₉ₕₛZₛC₆₇₅ⱼzBₑₜₐₜDFₘᵣgₚₚₑₐₓᵢⱼcᵣ₀q₄ₗ₁w₂dᵥ",
+            converter.convert(&source, Font::Subscript)
+        );
+    }
+
+    #[test]
+    fn greek() {
+        let converter = setup_converter();
+        let source = "ΑΡϴΣΩ∇αςω∂ϵϑϰϕϱϖ".chars().collect::<Vec<_>>();
+        let expected = [
+            (Font::Bold, "𝚨𝚸𝚹𝚺𝛀𝛁𝛂𝛓𝛚𝛛𝛜𝛝𝛞𝛟𝛠𝛡"),
+            (Font::Italic, "𝛢𝛲𝛳𝛴𝛺𝛻𝛼𝜍𝜔𝜕𝜖𝜗𝜘𝜙𝜚𝜛"),
+            (Font::BoldItalic, "𝜜𝜬𝜭𝜮𝜴𝜵𝜶𝝇𝝎𝝏𝝐𝝑𝝒𝝓𝝔𝝕"),
+            (Font::BoldSans, "𝝖𝝦𝝧𝝨𝝮𝝯𝝰𝞁𝞈𝞉𝞊𝞋𝞌𝞍𝞎𝞏"),
+            (Font::BoldItalicSans, "𝞐𝞠𝞡𝞢𝞨𝞩𝞪𝞻𝟂𝟃𝟄𝟅𝟆𝟇𝟈𝟉"),
+        ];
+        for (font, expected) in expected {
+            assert_eq!(expected, converter.convert(&source, font), "{font:?}");
+            assert_eq!(
+                source.iter().collect::<String>(),
+                converter.revert_str(expected),
+                "{font:?}"
+            );
+        }
+        assert_eq!("𝚤𝚥", converter.convert_str("ıȷ", Font::Italic));
+        assert_eq!("𝟊𝟋", converter.convert_str("Ϝϝ", Font::Bold));
+        // Greek letters are left as they are in other fonts.
+        assert_eq!("α", converter.convert_str("𝛂", Font::Script));
+    }
+
+    #[test]
+    #[cfg(feature = "decompose")]
+    fn decompose() {
+        let converter = setup_converter();
+        let source = "Ünïcödé, ĳ".chars().collect::<Vec<_>>();
+        let decomposed = converter.decompose(&source, Font::Bold);
+        assert_eq!("U\u{308}ni\u{308}co\u{308}de\u{301}, ĳ", decomposed);
+        assert_eq!(
+            "𝐔\u{308}𝐧𝐢\u{308}𝐜𝐨\u{308}𝐝𝐞\u{301}, ĳ",
+            converter.convert_str(&decomposed, Font::Bold)
+        );
+        assert_eq!("e\u{301}ø", converter.decompose_str("éø", Font::Script));
+        assert_eq!("éá", converter.decompose_str("éá", Font::FullStop));
+    }
+
+    #[test]
+    fn convert_chars() {
+        let converter = setup_converter();
+        let mut converted = converter.convert_chars("ab 1".chars(), Font::Emoji);
+        assert_eq!(Some('🇦'), converted.next());
+        assert_eq!(Some('\u{200C}'), converted.next());
+        assert_eq!(Some('🇧'), converted.next());
+        assert_eq!("\u{200C} \u{200C}1", converted.collect::<String>());
+        assert_eq!(
+            "🇪\u{301}\u{200C}🇦",
+            converter.convert_str("e\u{301}a", Font::Emoji)
+        );
+    }
+
+    #[test]
+    fn restyle() {
+        let converter = setup_converter();
+        let source = "𝐇𝐞𝐥𝐥𝐨, 𝟬𝟭 𝓎ℴ𝓊 and 🇾‌🇴‌🇺".chars().collect::<Vec<_>>();
+        assert_eq!(
+            "ℋℯ𝓁𝓁ℴ, 01 𝓎ℴ𝓊 𝒶𝓃𝒹 𝓎ℴ𝓊",
+            converter.convert(&source, Font::Script)
+        );
+    }
+
+    #[test]
+    fn restyle_flags() {
+        let converter = setup_converter();
+        assert_eq!("𝐈 🇯🇵 𝐱", converter.convert_str("I 🇯🇵 🇽", Font::Bold));
+        assert_eq!(
+            "🇮\u{200C} \u{200C}🇯🇵\u{200C}🇦",
+            converter.convert_str("I 🇯🇵\u{200C}𝐚", Font::Emoji)
+        );
+        // U+200C after regional indicator symbols does not affect the rest.
+        assert_eq!(
+            "𝐚𝐛 𝐜\u{200C}𝐝",
+            converter.convert_str("🇦\u{200C}🇧 c\u{200C}d", Font::Bold)
+        );
+    }
+
+    #[test]
+    fn restyle_symbols() {
+        let converter = setup_converter();
+        assert_eq!(
+            "𝐀𝐫𝐞𝐚: 𝟏𝟎 𝐦², ① ⒈ ᴀ",
+            converter.convert_str("Area: 10 m², ① ⒈ ᴀ", Font::Bold)
+        );
+        // Decoding reverts all fonts.
+        assert_eq!("x2 1 hello", converter.revert_str("x² ① ʜᴇʟʟᴏ"));
+    }
+
+    #[test]
+    fn restyle_japanese() {
+        let converter = setup_converter();
+        let source = "ＡＢＣ、\u{3000}こんにちは！".chars().collect::<Vec<_>>();
+        assert_eq!(
+            "𝐀𝐁𝐂、\u{3000}こんにちは！",
+            converter.convert(&source, Font::Bold)
+        );
+    }
+
+    #[test]
+    fn revert() {
+        let converter = setup_converter();
+        let source = "8WymXbLV3nINUhOoQkKGfuY9HsZSC675jzBEtATDFMRgPpeaxiJcr0q4l1w2dv"
+            .chars()
+            .collect::<Vec<_>>();
+        for &font in Font::ALL {
+            if font == Font::Emoji {
+                continue;
+            }
+            let converted = converter.convert(&source, font);
+            let reverted = converter.revert(&converted.chars().collect::<Vec<_>>());
+            let expected = source.iter().collect::<String>();
+            match font {
+                // Fonts without small letters revert to capital ones.
+                Font::NegativeCircled | Font::Squared | Font::NegativeSquared => {
+                    assert_eq!(expected.to_uppercase(), reverted, "{font:?}")
+                }
+                // Some capitals share characters with small letters, so only check that the
+                // reverted text is converted to the same one.
+                Font::Superscript | Font::Subscript => {
+                    assert_eq!(
+                        converted,
+                        converter.convert_str(&reverted, font),
+                        "{font:?}"
+                    )
+                }
+                _ => assert_eq!(expected, reverted, "{font:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn revert_emoji() {
+        let converter = setup_converter();
+        let source = "8‌🇼‌🇾‌🇲‌ ‌🇽".chars().collect::<Vec<_>>();
+        assert_eq!("8wym x", converter.revert(&source));
+        // U+200C not inserted for `Font::Emoji` and flags are kept.
+        assert_eq!("می\u{200C}خواهم", converter.revert_str("می\u{200C}خواهم"));
+        assert_eq!("🇯🇵 x", converter.revert_str("🇯🇵 🇽"));
+    }
+
+    #[test]
+    fn detect() {
+        let converter = setup_converter();
+        let source = "𝐇𝐞𝐥𝐥𝐨".chars().collect::<Vec<_>>();
+        assert_eq!(
+            vec![DetectedSpan {
+                fonts: vec![Font::Bold],
+                text: "𝐇𝐞𝐥𝐥𝐨".to_string(),
+            }],
+            converter.detect(&source)
+        );
+    }
+
+    #[test]
+    fn detect_mixed() {
+        let converter = setup_converter();
+        let source = "𝐇𝐢, 𝟬𝟭 𝘸𝘰𝘳𝘭𝘥 and 𝓎ℴ𝓊!".chars().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                DetectedSpan {
+                    fonts: vec![Font::Bold],
+                    text: "𝐇𝐢, ".to_string(),
+                },
+                DetectedSpan {
+                    fonts: vec![Font::ItalicSans],
+                    text: "𝟬𝟭 𝘸𝘰𝘳𝘭𝘥 ".to_string(),
+                },
+                DetectedSpan {
+                    fonts: vec![],
+                    text: "and ".to_string(),
+                },
+                DetectedSpan {
+                    fonts: vec![Font::Script],
+                    text: "𝓎ℴ𝓊!".to_string(),
+                },
+            ],
+            converter.detect(&source)
+        );
+    }
 }
@@ -0,0 +1,217 @@
+use core::iter::Peekable;
+
+use crate::convert::Converter;
+
+/// Line drawn along characters with a combining character placed after each of them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Decoration {
+    Underline,
+    DoubleUnderline,
+    Strikethrough,
+    Overline,
+}
+
+impl Decoration {
+    /// All decorations in the order they are applied.
+    pub const ALL: &'static [Decoration] = &[
+        Decoration::Underline,
+        Decoration::DoubleUnderline,
+        Decoration::Strikethrough,
+        Decoration::Overline,
+    ];
+
+    /// Combining character which draws the line.
+    pub fn mark(&self) -> char {
+        match self {
+            Decoration::Underline => '\u{0332}',
+            Decoration::DoubleUnderline => '\u{0333}',
+            Decoration::Strikethrough => '\u{0336}',
+            Decoration::Overline => '\u{0305}',
+        }
+    }
+}
+
+/// Set of decorations.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Decorations(u8);
+
+impl Decorations {
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    fn bit(decoration: Decoration) -> u8 {
+        1 << decoration as u8
+    }
+
+    pub fn insert(&mut self, decoration: Decoration) {
+        self.0 |= Self::bit(decoration);
+    }
+
+    pub fn contains(&self, decoration: Decoration) -> bool {
+        self.0 & Self::bit(decoration) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over decorations in the order they are applied.
+    pub fn iter(&self) -> impl Iterator<Item = Decoration> + '_ {
+        Decoration::ALL
+            .iter()
+            .copied()
+            .filter(|&decoration| self.contains(decoration))
+    }
+
+    /// Remove and return the first decoration.
+    fn pop_first(&mut self) -> Option<Decoration> {
+        let first = self.iter().next()?;
+        self.0 &= !Self::bit(first);
+        Some(first)
+    }
+}
+
+impl From<Decoration> for Decorations {
+    fn from(decoration: Decoration) -> Self {
+        let mut decorations = Self::new();
+        decorations.insert(decoration);
+        decorations
+    }
+}
+
+impl From<&[Decoration]> for Decorations {
+    fn from(decorations: &[Decoration]) -> Self {
+        decorations.iter().copied().collect()
+    }
+}
+
+impl<const N: usize> From<&[Decoration; N]> for Decorations {
+    fn from(decorations: &[Decoration; N]) -> Self {
+        decorations.iter().copied().collect()
+    }
+}
+
+impl FromIterator<Decoration> for Decorations {
+    fn from_iter<T: IntoIterator<Item = Decoration>>(iter: T) -> Self {
+        let mut decorations = Self::new();
+        for decoration in iter {
+            decorations.insert(decoration);
+        }
+        decorations
+    }
+}
+
+/// Whether the character is a combining diacritical mark, which belongs to the preceding one.
+pub(crate) fn is_combining(c: &char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+impl Converter {
+    /// Decorate given characters with lines.
+    /// Combining characters are placed after existing ones, and control characters such as
+    /// newlines and U+200C inserted for `Font::Emoji` are not decorated.
+    #[cfg(feature = "alloc")]
+    pub fn decorate(
+        &self,
+        source: &[char],
+        decorations: impl Into<Decorations>,
+    ) -> alloc::string::String {
+        self.decorate_chars(source.iter().copied(), decorations)
+            .collect()
+    }
+
+    /// Same as `Converter::decorate`, but takes a string slice.
+    #[cfg(feature = "alloc")]
+    pub fn decorate_str(
+        &self,
+        source: &str,
+        decorations: impl Into<Decorations>,
+    ) -> alloc::string::String {
+        self.decorate_chars(source.chars(), decorations).collect()
+    }
+
+    /// Same as `Converter::decorate`, but lazily yields decorated characters without allocation.
+    /// This can be chained with `Converter::convert_chars` to decorate text in a font.
+    pub fn decorate_chars<I>(
+        &self,
+        source: I,
+        decorations: impl Into<Decorations>,
+    ) -> DecorateChars<I::IntoIter>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        DecorateChars {
+            source: source.into_iter().peekable(),
+            decorations: decorations.into(),
+            pending: Decorations::new(),
+        }
+    }
+}
+
+/// Iterator returned by `Converter::decorate_chars`.
+#[derive(Debug, Clone)]
+pub struct DecorateChars<I: Iterator<Item = char>> {
+    source: Peekable<I>,
+    decorations: Decorations,
+    /// Decorations to yield after the current character.
+    pending: Decorations,
+}
+
+impl<I: Iterator<Item = char>> Iterator for DecorateChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(decoration) = self.pending.pop_first() {
+            return Some(decoration.mark());
+        }
+
+        let c = self.source.next()?;
+        if !(c.is_control() || c == '\u{200C}' || self.source.peek().is_some_and(is_combining)) {
+            self.pending = self.decorations;
+        }
+        Some(c)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::font::Font;
+    use alloc::string::String;
+
+    #[test]
+    fn decorate() {
+        let converter = Converter::new();
+        assert_eq!(
+            "a\u{332}b\u{332} \u{332}c\u{332}\n",
+            converter.decorate_str("ab c\n", &[Decoration::Underline])
+        );
+        assert_eq!(
+            "e\u{301}\u{336}\u{305}",
+            converter.decorate_str(
+                "e\u{301}",
+                &[Decoration::Strikethrough, Decoration::Overline]
+            )
+        );
+    }
+
+    #[test]
+    fn decorate_font() {
+        let converter = Converter::new();
+        let decorated = converter
+            .decorate_chars(
+                converter.convert_chars("ab".chars(), Font::Emoji),
+                &[Decoration::DoubleUnderline],
+            )
+            .collect::<String>();
+        assert_eq!("🇦\u{333}\u{200C}🇧\u{333}", decorated);
+    }
+}
@@ -0,0 +1,332 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use crate::convert::{Converter, Normalize};
+use crate::font::Font;
+#[cfg(feature = "alloc")]
+use crate::style::Style;
+
+/// How to handle characters which cannot be converted to a font.
+///
+/// A character cannot be converted if it is alphanumeric and the target font does not have it
+/// while some fonts have it or it is a Latin or Greek letter, e.g. digits in `Font::Script` and
+/// accented letters, which `Converter::decompose` helps decorate. Other characters such as
+/// symbols and letters of scripts without fonts are always kept, and so are characters which the
+/// target font leaves unchanged by design, e.g. capitals in `Font::SmallCaps`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// Leave the characters as they are.
+    #[default]
+    Keep,
+    /// Remove the characters.
+    Drop,
+    /// Replace the characters with given one.
+    Replace(char),
+    /// Fail to convert. Iterators such as `Converter::convert_chars_with` keep the characters
+    /// instead, so use `Converter::try_convert` or `Converter::try_stylize` to find them.
+    Error,
+    /// Use characters of the first font in `Font::fallbacks` which has them, or leave them as
+    /// they are if none of the fonts has them.
+    Nearest,
+}
+
+impl Fallback {
+    /// Convert the normal character to the font, returning `None` if it is dropped.
+    pub(crate) fn convert_char(&self, c: char, font: Font) -> Option<char> {
+        if let Some(converted) = font.characters().get(c) {
+            return Some(converted);
+        }
+        if !is_unmappable(c, font) {
+            return Some(c);
+        }
+        match *self {
+            Fallback::Keep | Fallback::Error => Some(c),
+            Fallback::Drop => None,
+            Fallback::Replace(replacement) => Some(replacement),
+            Fallback::Nearest => Some(
+                font.fallbacks()
+                    .iter()
+                    .find_map(|fallback| fallback.characters().get(c))
+                    .unwrap_or(c),
+            ),
+        }
+    }
+}
+
+/// Whether the normal character cannot be converted to the font.
+fn is_unmappable(c: char, font: Font) -> bool {
+    c.is_alphanumeric()
+        && font.characters().get(c).is_none()
+        && !font.keeps(c)
+        && (is_latin_or_greek(c)
+            || Font::ALL
+                .iter()
+                .any(|other| other.characters().get(c).is_some()))
+}
+
+/// Whether the character is in blocks of Latin or Greek letters, which fonts decorate.
+fn is_latin_or_greek(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00C0}'..='\u{024F}'
+            | '\u{0370}'..='\u{03FF}'
+            | '\u{1E00}'..='\u{1FFF}'
+            | '\u{2C60}'..='\u{2C7F}'
+            | '\u{A720}'..='\u{A7FF}'
+    )
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fallback::Keep => f.write_str("keep"),
+            Fallback::Drop => f.write_str("drop"),
+            Fallback::Replace(replacement) => write!(f, "replace={replacement}"),
+            Fallback::Error => f.write_str("error"),
+            Fallback::Nearest => f.write_str("nearest"),
+        }
+    }
+}
+
+/// Error returned when parsing a string which is not a fallback policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFallbackError(());
+
+impl fmt::Display for ParseFallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected one of keep, drop, replace=<CHAR>, error and nearest")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseFallbackError {}
+
+impl FromStr for Fallback {
+    type Err = ParseFallbackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(replacement) = s.strip_prefix("replace=") {
+            let mut chars = replacement.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(replacement), None) => Ok(Fallback::Replace(replacement)),
+                _ => Err(ParseFallbackError(())),
+            };
+        }
+        [
+            ("keep", Fallback::Keep),
+            ("drop", Fallback::Drop),
+            ("error", Fallback::Error),
+            ("nearest", Fallback::Nearest),
+        ]
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|(_, fallback)| fallback)
+        .ok_or(ParseFallbackError(()))
+    }
+}
+
+/// Error returned when the input has characters which cannot be converted to the font.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertError {
+    font: Font,
+    characters: Vec<char>,
+}
+
+#[cfg(feature = "alloc")]
+impl ConvertError {
+    /// The font which the input is converted to.
+    pub fn font(&self) -> Font {
+        self.font
+    }
+
+    /// Characters which cannot be converted, in the order of appearance without duplicates.
+    pub fn characters(&self) -> &[char] {
+        &self.characters
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not have ", self.font)?;
+        for (i, c) in self.characters.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "'{c}'")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConvertError {}
+
+#[cfg(feature = "alloc")]
+impl Converter {
+    /// Convert given characters to specified font, handling characters which the font does not
+    /// have according to the fallback policy.
+    /// With `Fallback::Error`, an error listing such characters is returned if there are any.
+    pub fn try_convert(
+        &self,
+        source: &[char],
+        font: Font,
+        fallback: Fallback,
+    ) -> Result<String, ConvertError> {
+        check(source, font, fallback)?;
+        Ok(self
+            .convert_chars_with(source.iter().copied(), font, fallback)
+            .collect())
+    }
+
+    /// Same as `Converter::try_convert`, but takes a string slice.
+    pub fn try_convert_str(
+        &self,
+        source: &str,
+        font: Font,
+        fallback: Fallback,
+    ) -> Result<String, ConvertError> {
+        self.try_convert(&source.chars().collect::<Vec<_>>(), font, fallback)
+    }
+
+    /// Same as `Converter::stylize`, but returns an error listing characters which the font of
+    /// the style does not have if its fallback policy is `Fallback::Error`.
+    pub fn try_stylize(&self, source: &[char], style: Style) -> Result<String, ConvertError> {
+        if let Some(font) = style.font {
+            check(source, font, style.fallback)?;
+        }
+        Ok(self.stylize(source, style))
+    }
+
+    /// Same as `Converter::try_stylize`, but takes a string slice.
+    pub fn try_stylize_str(&self, source: &str, style: Style) -> Result<String, ConvertError> {
+        self.try_stylize(&source.chars().collect::<Vec<_>>(), style)
+    }
+}
+
+/// With `Fallback::Error`, fail if the font does not have some of given characters.
+#[cfg(feature = "alloc")]
+fn check(source: &[char], font: Font, fallback: Fallback) -> Result<(), ConvertError> {
+    if fallback == Fallback::Error {
+        let mut characters = Vec::new();
+        for c in Normalize::new(source.iter().copied(), Converter::normalize_char) {
+            if is_unmappable(c, font) && !characters.contains(&c) {
+                characters.push(c);
+            }
+        }
+        if !characters.is_empty() {
+            return Err(ConvertError { font, characters });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse() {
+        for fallback in [
+            Fallback::Keep,
+            Fallback::Drop,
+            Fallback::Replace('?'),
+            Fallback::Replace('='),
+            Fallback::Error,
+            Fallback::Nearest,
+        ] {
+            assert_eq!(Ok(fallback), fallback.to_string().parse());
+        }
+        assert!("replace=".parse::<Fallback>().is_err());
+        assert!("replace=ab".parse::<Fallback>().is_err());
+        assert!("ignore".parse::<Fallback>().is_err());
+    }
+
+    #[test]
+    fn fallback() {
+        let converter = Converter::new();
+        let source = "Top 10 αβ あ!";
+        let convert = |fallback| converter.try_convert_str(source, Font::Script, fallback);
+        assert_eq!(Ok("𝒯ℴ𝓅 10 αβ あ!".to_string()), convert(Fallback::Keep));
+        assert_eq!(Ok("𝒯ℴ𝓅   あ!".to_string()), convert(Fallback::Drop));
+        assert_eq!(
+            Ok("𝒯ℴ𝓅 __ __ あ!".to_string()),
+            convert(Fallback::Replace('_'))
+        );
+        assert_eq!(Ok("𝒯ℴ𝓅 𝟭𝟬 𝛼𝛽 あ!".to_string()), convert(Fallback::Nearest));
+
+        let error = convert(Fallback::Error).unwrap_err();
+        assert_eq!(Font::Script, error.font());
+        assert_eq!(&['1', '0', 'α', 'β'], error.characters());
+        assert_eq!("script does not have '1', '0', 'α', 'β'", error.to_string());
+        assert!(converter
+            .try_convert_str("Top 10", Font::Bold, Fallback::Error)
+            .is_ok());
+        assert_eq!(
+            &['é'],
+            converter
+                .try_convert_str("café", Font::Bold, Fallback::Error)
+                .unwrap_err()
+                .characters()
+        );
+        assert!(converter
+            .try_convert_str("cafe\u{301}", Font::Bold, Fallback::Error)
+            .is_ok());
+    }
+
+    #[test]
+    fn fallback_kept_by_design() {
+        let converter = Converter::new();
+        assert_eq!(
+            Ok("Hᴇʟʟᴏ Wᴏʀʟᴅ 1".to_string()),
+            converter.try_convert_str("Hello World 1", Font::SmallCaps, Fallback::Drop)
+        );
+        assert_eq!(
+            Ok("ᵉq".to_string()),
+            converter.try_convert_str("eq", Font::Superscript, Fallback::Error)
+        );
+        assert_eq!(
+            &['b'],
+            converter
+                .try_convert_str("xb", Font::Subscript, Fallback::Error)
+                .unwrap_err()
+                .characters()
+        );
+        assert_eq!(
+            Ok("⒜".to_string()),
+            converter.try_convert_str("a0", Font::Parenthesized, Fallback::Drop)
+        );
+    }
+
+    #[test]
+    fn fallback_decorated() {
+        let converter = Converter::new();
+        assert_eq!(
+            Ok("𝒶𝒷".to_string()),
+            converter.try_convert_str("𝐚𝐛𝟏", Font::Script, Fallback::Drop)
+        );
+        assert_eq!(
+            Ok("🇦\u{200C}🇧\u{200C}①".to_string()),
+            converter.try_convert_str("ab1", Font::Emoji, Fallback::Nearest)
+        );
+    }
+
+    #[test]
+    fn try_stylize() {
+        let converter = Converter::new();
+        let style = Style::from(Font::Script).with_fallback(Fallback::Error);
+        assert_eq!(Ok("𝒶𝒷".to_string()), converter.try_stylize_str("ab", style));
+        assert_eq!(
+            &['1'],
+            converter
+                .try_stylize_str("a1", style)
+                .unwrap_err()
+                .characters()
+        );
+    }
+}
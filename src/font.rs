@@ -1,9 +1,7 @@
-use clap::ValueEnum;
-use std::collections::HashMap;
+use core::fmt;
+use core::str::FromStr;
 
-pub type FontMap = HashMap<char, char>;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Font {
     Bold,
     Italic,
@@ -19,32 +17,670 @@ pub enum Font {
     Monospace,
     Blackboard,
     Emoji,
+    Circled,
+    NegativeCircled,
+    Squared,
+    NegativeSquared,
+    /// Letters and digits from 1 to 9 in parentheses. Digit 0 is not available.
+    Parenthesized,
+    /// Digits followed by a full stop. Letters are not available.
+    FullStop,
+    /// Fullwidth forms, which also cover punctuation and space unlike other fonts.
+    Fullwidth,
+    /// Small capital letters. Capitals, digits and small x remain unchanged.
+    SmallCaps,
+    /// Superscript letters, digits and `+-=()`. Capitals without superscript forms are
+    /// converted to small ones, and small q remains unchanged.
+    Superscript,
+    /// Subscript digits, `+-=()` and letters which have subscript forms, regardless of their case.
+    Subscript,
 }
 
 impl Font {
-    pub fn characters(&self) -> FontMap {
-        let source = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-        let target = match self {
-            Font::Bold => "𝐚𝐛𝐜𝐝𝐞𝐟𝐠𝐡𝐢𝐣𝐤𝐥𝐦𝐧𝐨𝐩𝐪𝐫𝐬𝐭𝐮𝐯𝐰𝐱𝐲𝐳𝐀𝐁𝐂𝐃𝐄𝐅𝐆𝐇𝐈𝐉𝐊𝐋𝐌𝐍𝐎𝐏𝐐𝐑𝐒𝐓𝐔𝐕𝐖𝐗𝐘𝐙𝟎𝟏𝟐𝟑𝟒𝟓𝟔𝟕𝟖𝟗",
-            Font::Italic => "𝑎𝑏𝑐𝑑𝑒𝑓𝑔ℎ𝑖𝑗𝑘𝑙𝑚𝑛𝑜𝑝𝑞𝑟𝑠𝑡𝑢𝑣𝑤𝑥𝑦𝑧𝐴𝐵𝐶𝐷𝐸𝐹𝐺𝐻𝐼𝐽𝐾𝐿𝑀𝑁𝑂𝑃𝑄𝑅𝑆𝑇𝑈𝑉𝑊𝑋𝑌𝑍0123456789",
-            Font::BoldItalic => "𝒂𝒃𝒄𝒅𝒆𝒇𝒈𝒉𝒊𝒋𝒌𝒍𝒎𝒏𝒐𝒑𝒒𝒓𝒔𝒕𝒖𝒗𝒘𝒙𝒚𝒛𝑨𝑩𝑪𝑫𝑬𝑭𝑮𝑯𝑰𝑱𝑲𝑳𝑴𝑵𝑶𝑷𝑸𝑹𝑺𝑻𝑼𝑽𝑾𝑿𝒀𝒁0123456789",
-            Font::Sans => "𝖺𝖻𝖼𝖽𝖾𝖿𝗀𝗁𝗂𝗃𝗄𝗅𝗆𝗇𝗈𝗉𝗊𝗋𝗌𝗍𝗎𝗏𝗐𝗑𝗒𝗓𝖠𝖡𝖢𝖣𝖤𝖥𝖦𝖧𝖨𝖩𝖪𝖫𝖬𝖭𝖮𝖯𝖰𝖱𝖲𝖳𝖴𝖵𝖶𝖷𝖸𝖹𝟢𝟣𝟤𝟥𝟦𝟧𝟨𝟩𝟪𝟫",
-            Font::BoldSans => "𝗮𝗯𝗰𝗱𝗲𝗳𝗴𝗵𝗶𝗷𝗸𝗹𝗺𝗻𝗼𝗽𝗾𝗿𝘀𝘁𝘂𝘃𝘄𝘅𝘆𝘇𝗔𝗕𝗖𝗗𝗘𝗙𝗚𝗛𝗜𝗝𝗞𝗟𝗠𝗡𝗢𝗣𝗤𝗥𝗦𝗧𝗨𝗩𝗪𝗫𝗬𝗭𝟬𝟭𝟮𝟯𝟰𝟱𝟲𝟳𝟴𝟵",
-            Font::ItalicSans => "𝘢𝘣𝘤𝘥𝘦𝘧𝘨𝘩𝘪𝘫𝘬𝘭𝘮𝘯𝘰𝘱𝘲𝘳𝘴𝘵𝘶𝘷𝘸𝘹𝘺𝘻𝘈𝘉𝘊𝘋𝘌𝘍𝘎𝘏𝘐𝘑𝘒𝘓𝘔𝘕𝘖𝘗𝘘𝘙𝘚𝘛𝘜𝘝𝘞𝘟𝘠𝘡𝟬𝟭𝟮𝟯𝟰𝟱𝟲𝟳𝟴𝟵",
-            Font::BoldItalicSans => {
-                "𝙖𝙗𝙘𝙙𝙚𝙛𝙜𝙝𝙞𝙟𝙠𝙡𝙢𝙣𝙤𝙥𝙦𝙧𝙨𝙩𝙪𝙫𝙬𝙭𝙮𝙯𝘼𝘽𝘾𝘿𝙀𝙁𝙂𝙃𝙄𝙅𝙆𝙇𝙈𝙉𝙊𝙋𝙌𝙍𝙎𝙏𝙐𝙑𝙒𝙓𝙔𝙕𝟬𝟭𝟮𝟯𝟰𝟱𝟲𝟳𝟴𝟵"
-            }
-            Font::Script => "𝒶𝒷𝒸𝒹ℯ𝒻ℊ𝒽𝒾𝒿𝓀𝓁𝓂𝓃ℴ𝓅𝓆𝓇𝓈𝓉𝓊𝓋𝓌𝓍𝓎𝓏𝒜ℬ𝒞𝒟ℰℱ𝒢ℋℐ𝒥𝒦ℒℳ𝒩𝒪𝒫𝒬ℛ𝒮𝒯𝒰𝒱𝒲𝒳𝒴𝒵0123456789",
-            Font::BoldScript => "𝓪𝓫𝓬𝓭𝓮𝓯𝓰𝓱𝓲𝓳𝓴𝓵𝓶𝓷𝓸𝓹𝓺𝓻𝓼𝓽𝓾𝓿𝔀𝔁𝔂𝔃𝓐𝓑𝓒𝓓𝓔𝓕𝓖𝓗𝓘𝓙𝓚𝓛𝓜𝓝𝓞𝓟𝓠𝓡𝓢𝓣𝓤𝓥𝓦𝓧𝓨𝓩0123456789",
-            Font::Fraktur => "𝔞𝔟𝔠𝔡𝔢𝔣𝔤𝔥𝔦𝔧𝔨𝔩𝔪𝔫𝔬𝔭𝔮𝔯𝔰𝔱𝔲𝔳𝔴𝔵𝔶𝔷𝔄𝔅ℭ𝔇𝔈𝔉𝔊ℌℑ𝔍𝔎𝔏𝔐𝔑𝔒𝔓𝔔ℜ𝔖𝔗𝔘𝔙𝔚𝔛𝔜ℨ0123456789",
-            Font::BoldFraktur => "𝖆𝖇𝖈𝖉𝖊𝖋𝖌𝖍𝖎𝖏𝖐𝖑𝖒𝖓𝖔𝖕𝖖𝖗𝖘𝖙𝖚𝖛𝖜𝖝𝖞𝖟𝕬𝕭𝕮𝕯𝕰𝕱𝕲𝕳𝕴𝕵𝕶𝕷𝕸𝕹𝕺𝕻𝕼𝕽𝕾𝕿𝖀𝖁𝖂𝖃𝖄𝖅0123456789",
-            Font::Monospace => "𝚊𝚋𝚌𝚍𝚎𝚏𝚐𝚑𝚒𝚓𝚔𝚕𝚖𝚗𝚘𝚙𝚚𝚛𝚜𝚝𝚞𝚟𝚠𝚡𝚢𝚣𝙰𝙱𝙲𝙳𝙴𝙵𝙶𝙷𝙸𝙹𝙺𝙻𝙼𝙽𝙾𝙿𝚀𝚁𝚂𝚃𝚄𝚅𝚆𝚇𝚈𝚉𝟶𝟷𝟸𝟹𝟺𝟻𝟼𝟽𝟾𝟿",
-            Font::Blackboard => "𝕒𝕓𝕔𝕕𝕖𝕗𝕘𝕙𝕚𝕛𝕜𝕝𝕞𝕟𝕠𝕡𝕢𝕣𝕤𝕥𝕦𝕧𝕨𝕩𝕪𝕫𝔸𝔹ℂ𝔻𝔼𝔽𝔾ℍ𝕀𝕁𝕂𝕃𝕄ℕ𝕆ℙℚℝ𝕊𝕋𝕌𝕍𝕎𝕏𝕐ℤ𝟘𝟙𝟚𝟛𝟜𝟝𝟞𝟟𝟠𝟡",
-            // Following characters are regional indicator symbols, which is rendered as roman characters surrounded by square in some Websites.
-            // In some editor such as VSCode, A pair 'Z' and 'A' is rendered as South Africa flag.
-            Font::Emoji => "🇦🇧🇨🇩🇪🇫🇬🇭🇮🇯🇰🇱🇲🇳🇴🇵🇶🇷🇸🇹🇺🇻🇼🇽🇾🇿🇦🇧🇨🇩🇪🇫🇬🇭🇮🇯🇰🇱🇲🇳🇴🇵🇶🇷🇸🇹🇺🇻🇼🇽🇾🇿0123456789",
-        };
-
-        source.chars().zip(target.chars()).collect()
+    /// All fonts in the order shown to users.
+    pub const ALL: &'static [Font] = &[
+        Font::Bold,
+        Font::Italic,
+        Font::BoldItalic,
+        Font::Sans,
+        Font::BoldSans,
+        Font::ItalicSans,
+        Font::BoldItalicSans,
+        Font::Script,
+        Font::BoldScript,
+        Font::Fraktur,
+        Font::BoldFraktur,
+        Font::Monospace,
+        Font::Blackboard,
+        Font::Emoji,
+        Font::Circled,
+        Font::NegativeCircled,
+        Font::Squared,
+        Font::NegativeSquared,
+        Font::Parenthesized,
+        Font::FullStop,
+        Font::Fullwidth,
+        Font::SmallCaps,
+        Font::Superscript,
+        Font::Subscript,
+    ];
+
+    /// Name of the font used in the command line, e.g. `bold-italic`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Font::Bold => "bold",
+            Font::Italic => "italic",
+            Font::BoldItalic => "bold-italic",
+            Font::Sans => "sans",
+            Font::BoldSans => "bold-sans",
+            Font::ItalicSans => "italic-sans",
+            Font::BoldItalicSans => "bold-italic-sans",
+            Font::Script => "script",
+            Font::BoldScript => "bold-script",
+            Font::Fraktur => "fraktur",
+            Font::BoldFraktur => "bold-fraktur",
+            Font::Monospace => "monospace",
+            Font::Blackboard => "blackboard",
+            Font::Emoji => "emoji",
+            Font::Circled => "circled",
+            Font::NegativeCircled => "negative-circled",
+            Font::Squared => "squared",
+            Font::NegativeSquared => "negative-squared",
+            Font::Parenthesized => "parenthesized",
+            Font::FullStop => "full-stop",
+            Font::Fullwidth => "fullwidth",
+            Font::SmallCaps => "small-caps",
+            Font::Superscript => "superscript",
+            Font::Subscript => "subscript",
+        }
+    }
+
+    /// Mapping between normal characters and ones of the font.
+    /// Fonts may not cover all alphanumeric characters, e.g. `Font::Script` has no digits.
+    pub fn characters(&self) -> &'static FontMap {
+        match self {
+            Font::Bold => &BOLD,
+            Font::Italic => &ITALIC,
+            Font::BoldItalic => &BOLD_ITALIC,
+            Font::Sans => &SANS,
+            Font::BoldSans => &BOLD_SANS,
+            Font::ItalicSans => &ITALIC_SANS,
+            Font::BoldItalicSans => &BOLD_ITALIC_SANS,
+            Font::Script => &SCRIPT,
+            Font::BoldScript => &BOLD_SCRIPT,
+            Font::Fraktur => &FRAKTUR,
+            Font::BoldFraktur => &BOLD_FRAKTUR,
+            Font::Monospace => &MONOSPACE,
+            Font::Blackboard => &BLACKBOARD,
+            Font::Emoji => &EMOJI,
+            Font::Circled => &CIRCLED,
+            Font::NegativeCircled => &NEGATIVE_CIRCLED,
+            Font::Squared => &SQUARED,
+            Font::NegativeSquared => &NEGATIVE_SQUARED,
+            Font::Parenthesized => &PARENTHESIZED,
+            Font::FullStop => &FULL_STOP,
+            Font::Fullwidth => &FULLWIDTH,
+            Font::SmallCaps => &SMALL_CAPS,
+            Font::Superscript => &SUPERSCRIPT,
+            Font::Subscript => &SUBSCRIPT,
+        }
+    }
+
+    /// Fonts whose characters are used in place of ones this font does not have, in order of
+    /// preference. Fonts similar in weight and slant come first.
+    pub fn fallbacks(&self) -> &'static [Font] {
+        match self {
+            Font::Bold => &[Font::BoldSans],
+            Font::Italic => &[Font::ItalicSans],
+            Font::BoldItalic => &[Font::BoldItalicSans, Font::Bold],
+            Font::Sans => &[Font::BoldSans],
+            Font::BoldSans => &[Font::Bold],
+            Font::ItalicSans => &[Font::Italic, Font::BoldItalicSans],
+            Font::BoldItalicSans => &[Font::BoldItalic, Font::BoldSans],
+            Font::Script => &[Font::Italic, Font::ItalicSans],
+            Font::BoldScript => &[Font::BoldItalic, Font::BoldItalicSans],
+            Font::Fraktur | Font::BoldFraktur | Font::Monospace | Font::Blackboard => &[Font::Bold],
+            Font::Emoji | Font::Squared | Font::Parenthesized => &[Font::Circled],
+            Font::NegativeSquared => &[Font::NegativeCircled],
+            Font::FullStop => &[Font::Parenthesized, Font::Circled],
+            Font::Circled
+            | Font::NegativeCircled
+            | Font::Fullwidth
+            | Font::SmallCaps
+            | Font::Superscript
+            | Font::Subscript => &[],
+        }
+    }
+
+    /// Whether the font leaves the normal character as it is by design, as documented for each
+    /// font, rather than lacking it. Such characters are not handled by `Fallback`.
+    pub(crate) fn keeps(&self, c: char) -> bool {
+        match self {
+            Font::SmallCaps => c.is_ascii_uppercase() || c.is_ascii_digit() || c == 'x',
+            Font::Superscript => c == 'q',
+            _ => false,
+        }
+    }
+
+    /// Whether characters of the font are converted when converting text to another font.
+    /// Fonts whose characters are also used as ordinary symbols, e.g. `²` in `m²` and `①` in
+    /// numbered lists, are left as they are, though decoding reverts them.
+    pub fn is_restyled(&self) -> bool {
+        !matches!(
+            self,
+            Font::Circled
+                | Font::NegativeCircled
+                | Font::Parenthesized
+                | Font::FullStop
+                | Font::SmallCaps
+                | Font::Superscript
+                | Font::Subscript
+        )
+    }
+}
+
+/// Consecutive characters mapped to consecutive characters starting from `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    first: char,
+    last: char,
+    target: char,
+}
+
+impl Block {
+    const fn new(first: char, last: char, target: char) -> Self {
+        Self {
+            first,
+            last,
+            target,
+        }
+    }
+
+    fn get(&self, c: char) -> Option<char> {
+        if (self.first..=self.last).contains(&c) {
+            char::from_u32(self.target as u32 + (c as u32 - self.first as u32))
+        } else {
+            None
+        }
+    }
+
+    fn revert(&self, c: char) -> Option<char> {
+        let offset = (c as u32).checked_sub(self.target as u32)?;
+        char::from_u32(self.first as u32 + offset).filter(|&reverted| reverted <= self.last)
+    }
+}
+
+/// Mapping between normal characters and ones of a font.
+///
+/// Most fonts place their characters in the same order as ASCII, so the mapping is described by
+/// blocks of consecutive characters. Characters placed elsewhere, such as ones in Letterlike
+/// Symbols (e.g. 'ℎ', 'ℬ' and 'ℭ'), are listed as exceptions, which take precedence over blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontMap {
+    blocks: &'static [Block],
+    exceptions: &'static [(char, char)],
+}
+
+impl FontMap {
+    const fn new(blocks: &'static [Block], exceptions: &'static [(char, char)]) -> Self {
+        Self { blocks, exceptions }
+    }
+
+    /// Get the character of the font corresponding to given normal character.
+    pub fn get(&self, c: char) -> Option<char> {
+        if let Some(&(_, decorated)) = self.exceptions.iter().find(|&&(original, _)| original == c)
+        {
+            return Some(decorated);
+        }
+        self.blocks.iter().find_map(|block| block.get(c))
+    }
+
+    /// Get the normal character corresponding to given character of the font.
+    /// If several characters share the same one, the first in the mapping is returned.
+    pub fn revert(&self, c: char) -> Option<char> {
+        if let Some(&(original, _)) = self
+            .exceptions
+            .iter()
+            .find(|&&(_, decorated)| decorated == c)
+        {
+            return Some(original);
+        }
+        // Characters in blocks which correspond to exceptions are holes in the font.
+        self.blocks
+            .iter()
+            .filter_map(|block| block.revert(c))
+            .find(|&reverted| {
+                !self
+                    .exceptions
+                    .iter()
+                    .any(|&(original, _)| original == reverted)
+            })
+    }
+}
+
+// Greek letters in the Mathematical Alphanumeric Symbols block have capital theta symbol 'ϴ'
+// in place of U+03A2, which is unassigned, and nabla '∇', partial differential '∂' and variant
+// letter forms around them.
+static BOLD: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝐚'),
+        Block::new('A', 'Z', '𝐀'),
+        Block::new('0', '9', '𝟎'),
+        Block::new('Α', 'Ρ', '𝚨'),
+        Block::new('Σ', 'Ω', '𝚺'),
+        Block::new('α', 'ω', '𝛂'),
+    ],
+    &[
+        ('ϴ', '𝚹'),
+        ('∇', '𝛁'),
+        ('∂', '𝛛'),
+        ('ϵ', '𝛜'),
+        ('ϑ', '𝛝'),
+        ('ϰ', '𝛞'),
+        ('ϕ', '𝛟'),
+        ('ϱ', '𝛠'),
+        ('ϖ', '𝛡'),
+        // Digamma is available only in bold.
+        ('Ϝ', '𝟊'),
+        ('ϝ', '𝟋'),
+    ],
+);
+static ITALIC: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝑎'),
+        Block::new('A', 'Z', '𝐴'),
+        Block::new('Α', 'Ρ', '𝛢'),
+        Block::new('Σ', 'Ω', '𝛴'),
+        Block::new('α', 'ω', '𝛼'),
+    ],
+    &[
+        ('h', 'ℎ'),
+        ('ϴ', '𝛳'),
+        ('∇', '𝛻'),
+        ('∂', '𝜕'),
+        ('ϵ', '𝜖'),
+        ('ϑ', '𝜗'),
+        ('ϰ', '𝜘'),
+        ('ϕ', '𝜙'),
+        ('ϱ', '𝜚'),
+        ('ϖ', '𝜛'),
+        // Dotless letters are available only in italic.
+        ('ı', '𝚤'),
+        ('ȷ', '𝚥'),
+    ],
+);
+static BOLD_ITALIC: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝒂'),
+        Block::new('A', 'Z', '𝑨'),
+        Block::new('Α', 'Ρ', '𝜜'),
+        Block::new('Σ', 'Ω', '𝜮'),
+        Block::new('α', 'ω', '𝜶'),
+    ],
+    &[
+        ('ϴ', '𝜭'),
+        ('∇', '𝜵'),
+        ('∂', '𝝏'),
+        ('ϵ', '𝝐'),
+        ('ϑ', '𝝑'),
+        ('ϰ', '𝝒'),
+        ('ϕ', '𝝓'),
+        ('ϱ', '𝝔'),
+        ('ϖ', '𝝕'),
+    ],
+);
+static SANS: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝖺'),
+        Block::new('A', 'Z', '𝖠'),
+        Block::new('0', '9', '𝟢'),
+    ],
+    &[],
+);
+static BOLD_SANS: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝗮'),
+        Block::new('A', 'Z', '𝗔'),
+        Block::new('0', '9', '𝟬'),
+        Block::new('Α', 'Ρ', '𝝖'),
+        Block::new('Σ', 'Ω', '𝝨'),
+        Block::new('α', 'ω', '𝝰'),
+    ],
+    &[
+        ('ϴ', '𝝧'),
+        ('∇', '𝝯'),
+        ('∂', '𝞉'),
+        ('ϵ', '𝞊'),
+        ('ϑ', '𝞋'),
+        ('ϰ', '𝞌'),
+        ('ϕ', '𝞍'),
+        ('ϱ', '𝞎'),
+        ('ϖ', '𝞏'),
+    ],
+);
+// There are no italic sans-serif numbers in Unicode, so bold ones are used instead.
+static ITALIC_SANS: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝘢'),
+        Block::new('A', 'Z', '𝘈'),
+        Block::new('0', '9', '𝟬'),
+    ],
+    &[],
+);
+static BOLD_ITALIC_SANS: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝙖'),
+        Block::new('A', 'Z', '𝘼'),
+        Block::new('0', '9', '𝟬'),
+        Block::new('Α', 'Ρ', '𝞐'),
+        Block::new('Σ', 'Ω', '𝞢'),
+        Block::new('α', 'ω', '𝞪'),
+    ],
+    &[
+        ('ϴ', '𝞡'),
+        ('∇', '𝞩'),
+        ('∂', '𝟃'),
+        ('ϵ', '𝟄'),
+        ('ϑ', '𝟅'),
+        ('ϰ', '𝟆'),
+        ('ϕ', '𝟇'),
+        ('ϱ', '𝟈'),
+        ('ϖ', '𝟉'),
+    ],
+);
+static SCRIPT: FontMap = FontMap::new(
+    &[Block::new('a', 'z', '𝒶'), Block::new('A', 'Z', '𝒜')],
+    &[
+        ('e', 'ℯ'),
+        ('g', 'ℊ'),
+        ('o', 'ℴ'),
+        ('B', 'ℬ'),
+        ('E', 'ℰ'),
+        ('F', 'ℱ'),
+        ('H', 'ℋ'),
+        ('I', 'ℐ'),
+        ('L', 'ℒ'),
+        ('M', 'ℳ'),
+        ('R', 'ℛ'),
+    ],
+);
+static BOLD_SCRIPT: FontMap =
+    FontMap::new(&[Block::new('a', 'z', '𝓪'), Block::new('A', 'Z', '𝓐')], &[]);
+static FRAKTUR: FontMap = FontMap::new(
+    &[Block::new('a', 'z', '𝔞'), Block::new('A', 'Z', '𝔄')],
+    &[('C', 'ℭ'), ('H', 'ℌ'), ('I', 'ℑ'), ('R', 'ℜ'), ('Z', 'ℨ')],
+);
+static BOLD_FRAKTUR: FontMap =
+    FontMap::new(&[Block::new('a', 'z', '𝖆'), Block::new('A', 'Z', '𝕬')], &[]);
+static MONOSPACE: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝚊'),
+        Block::new('A', 'Z', '𝙰'),
+        Block::new('0', '9', '𝟶'),
+    ],
+    &[],
+);
+static BLACKBOARD: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '𝕒'),
+        Block::new('A', 'Z', '𝔸'),
+        Block::new('0', '9', '𝟘'),
+    ],
+    &[
+        ('C', 'ℂ'),
+        ('H', 'ℍ'),
+        ('N', 'ℕ'),
+        ('P', 'ℙ'),
+        ('Q', 'ℚ'),
+        ('R', 'ℝ'),
+        ('Z', 'ℤ'),
+    ],
+);
+// Following characters are regional indicator symbols, which is rendered as roman characters surrounded by square in some Websites.
+// In some editor such as VSCode, A pair 'Z' and 'A' is rendered as South Africa flag.
+static EMOJI: FontMap = FontMap::new(&[Block::new('a', 'z', '🇦'), Block::new('A', 'Z', '🇦')], &[]);
+// Circled digit zero is placed apart from the others.
+static CIRCLED: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', 'ⓐ'),
+        Block::new('A', 'Z', 'Ⓐ'),
+        Block::new('1', '9', '①'),
+    ],
+    &[('0', '⓪')],
+);
+// There are no negative circled small letters in Unicode, so capital ones are used for both cases.
+static NEGATIVE_CIRCLED: FontMap = FontMap::new(
+    &[
+        Block::new('A', 'Z', '🅐'),
+        Block::new('a', 'z', '🅐'),
+        Block::new('1', '9', '❶'),
+    ],
+    &[('0', '⓿')],
+);
+// Squared fonts have only capital letters, which are used for small ones as well.
+// Unlike regional indicator symbols, adjacent ones are not combined into a flag.
+static SQUARED: FontMap =
+    FontMap::new(&[Block::new('A', 'Z', '🄰'), Block::new('a', 'z', '🄰')], &[]);
+static NEGATIVE_SQUARED: FontMap =
+    FontMap::new(&[Block::new('A', 'Z', '🅰'), Block::new('a', 'z', '🅰')], &[]);
+static PARENTHESIZED: FontMap = FontMap::new(
+    &[
+        Block::new('a', 'z', '⒜'),
+        Block::new('A', 'Z', '🄐'),
+        Block::new('1', '9', '⑴'),
+    ],
+    &[],
+);
+// Digit zero with a full stop is placed apart from the others.
+static FULL_STOP: FontMap = FontMap::new(&[Block::new('1', '9', '⒈')], &[('0', '🄀')]);
+// Ideographic space is used as fullwidth space.
+static FULLWIDTH: FontMap = FontMap::new(&[Block::new('!', '~', '！')], &[(' ', '\u{3000}')]);
+// Small capital letters are used only for small letters. There is no small capital x.
+static SMALL_CAPS: FontMap = FontMap::new(
+    &[],
+    &[
+        ('a', 'ᴀ'),
+        ('b', 'ʙ'),
+        ('c', 'ᴄ'),
+        ('d', 'ᴅ'),
+        ('e', 'ᴇ'),
+        ('f', 'ꜰ'),
+        ('g', 'ɢ'),
+        ('h', 'ʜ'),
+        ('i', 'ɪ'),
+        ('j', 'ᴊ'),
+        ('k', 'ᴋ'),
+        ('l', 'ʟ'),
+        ('m', 'ᴍ'),
+        ('n', 'ɴ'),
+        ('o', 'ᴏ'),
+        ('p', 'ᴘ'),
+        ('q', 'ꞯ'),
+        ('r', 'ʀ'),
+        ('s', 'ꜱ'),
+        ('t', 'ᴛ'),
+        ('u', 'ᴜ'),
+        ('v', 'ᴠ'),
+        ('w', 'ᴡ'),
+        ('y', 'ʏ'),
+        ('z', 'ᴢ'),
+    ],
+);
+// There is no superscript q. Capitals without superscript forms use small ones instead.
+static SUPERSCRIPT: FontMap = FontMap::new(
+    &[Block::new('4', '9', '⁴')],
+    &[
+        ('a', 'ᵃ'),
+        ('b', 'ᵇ'),
+        ('c', 'ᶜ'),
+        ('d', 'ᵈ'),
+        ('e', 'ᵉ'),
+        ('f', 'ᶠ'),
+        ('g', 'ᵍ'),
+        ('h', 'ʰ'),
+        ('i', 'ⁱ'),
+        ('j', 'ʲ'),
+        ('k', 'ᵏ'),
+        ('l', 'ˡ'),
+        ('m', 'ᵐ'),
+        ('n', 'ⁿ'),
+        ('o', 'ᵒ'),
+        ('p', 'ᵖ'),
+        ('r', 'ʳ'),
+        ('s', 'ˢ'),
+        ('t', 'ᵗ'),
+        ('u', 'ᵘ'),
+        ('v', 'ᵛ'),
+        ('w', 'ʷ'),
+        ('x', 'ˣ'),
+        ('y', 'ʸ'),
+        ('z', 'ᶻ'),
+        ('A', 'ᴬ'),
+        ('B', 'ᴮ'),
+        ('D', 'ᴰ'),
+        ('E', 'ᴱ'),
+        ('G', 'ᴳ'),
+        ('H', 'ᴴ'),
+        ('I', 'ᴵ'),
+        ('J', 'ᴶ'),
+        ('K', 'ᴷ'),
+        ('L', 'ᴸ'),
+        ('M', 'ᴹ'),
+        ('N', 'ᴺ'),
+        ('O', 'ᴼ'),
+        ('P', 'ᴾ'),
+        ('Q', 'ꟴ'),
+        ('R', 'ᴿ'),
+        ('T', 'ᵀ'),
+        ('U', 'ᵁ'),
+        ('V', 'ⱽ'),
+        ('W', 'ᵂ'),
+        ('C', 'ᶜ'),
+        ('F', 'ᶠ'),
+        ('S', 'ˢ'),
+        ('X', 'ˣ'),
+        ('Y', 'ʸ'),
+        ('Z', 'ᶻ'),
+        ('0', '⁰'),
+        ('1', '¹'),
+        ('2', '²'),
+        ('3', '³'),
+        ('+', '⁺'),
+        ('-', '⁻'),
+        ('=', '⁼'),
+        ('(', '⁽'),
+        (')', '⁾'),
+    ],
+);
+// Only some small letters have subscript forms, which are used for capitals as well.
+static SUBSCRIPT: FontMap = FontMap::new(
+    &[Block::new('0', '9', '₀')],
+    &[
+        ('a', 'ₐ'),
+        ('e', 'ₑ'),
+        ('h', 'ₕ'),
+        ('i', 'ᵢ'),
+        ('j', 'ⱼ'),
+        ('k', 'ₖ'),
+        ('l', 'ₗ'),
+        ('m', 'ₘ'),
+        ('n', 'ₙ'),
+        ('o', 'ₒ'),
+        ('p', 'ₚ'),
+        ('r', 'ᵣ'),
+        ('s', 'ₛ'),
+        ('t', 'ₜ'),
+        ('u', 'ᵤ'),
+        ('v', 'ᵥ'),
+        ('x', 'ₓ'),
+        ('A', 'ₐ'),
+        ('E', 'ₑ'),
+        ('H', 'ₕ'),
+        ('I', 'ᵢ'),
+        ('J', 'ⱼ'),
+        ('K', 'ₖ'),
+        ('L', 'ₗ'),
+        ('M', 'ₘ'),
+        ('N', 'ₙ'),
+        ('O', 'ₒ'),
+        ('P', 'ₚ'),
+        ('R', 'ᵣ'),
+        ('S', 'ₛ'),
+        ('T', 'ₜ'),
+        ('U', 'ᵤ'),
+        ('V', 'ᵥ'),
+        ('X', 'ₓ'),
+        ('+', '₊'),
+        ('-', '₋'),
+        ('=', '₌'),
+        ('(', '₍'),
+        (')', '₎'),
+    ],
+);
+
+impl fmt::Display for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing a string which is not a name of any font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFontError(());
+
+impl fmt::Display for ParseFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown font name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseFontError {}
+
+impl FromStr for Font {
+    type Err = ParseFontError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Font::ALL
+            .iter()
+            .find(|font| font.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(ParseFontError(()))
+    }
+}
+
+#[cfg(feature = "cli")]
+impl clap::ValueEnum for Font {
+    fn value_variants<'a>() -> &'a [Self] {
+        Font::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_name() {
+        for &font in Font::ALL {
+            assert_eq!(Ok(font), font.to_string().parse());
+        }
+        assert_eq!(Ok(Font::BoldItalic), "Bold-Italic".parse());
+        assert!("bold_italic".parse::<Font>().is_err());
+    }
+
+    #[test]
+    fn letterlike_holes() {
+        let italic = Font::Italic.characters();
+        assert_eq!(Some('ℎ'), italic.get('h'));
+        assert_eq!(Some('h'), italic.revert('ℎ'));
+        // U+1D455 is reserved for italic small h, which is placed at U+210E.
+        assert_eq!(None, italic.revert('\u{1D455}'));
+        assert_eq!(Some('C'), Font::Fraktur.characters().revert('ℭ'));
+        assert_eq!(None, Font::Bold.characters().get('あ'));
     }
 }
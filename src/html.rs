@@ -0,0 +1,306 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter;
+
+use crate::convert::Converter;
+use crate::decoration::{Decoration, Decorations};
+use crate::font::Font;
+use crate::markup::Emphasis;
+
+impl Converter {
+    /// Render HTML as plain text decorated with fonts, for channels which accept only plain text.
+    ///
+    /// `<b>`/`<strong>`, `<i>`/`<em>` and `<code>` are converted to `Font::Bold`, `Font::Italic`
+    /// and `Font::Monospace` respectively, and `<u>` underlines text with combining characters.
+    /// Other tags are stripped, except that line breaks and ends of blocks such as `<p>` start new
+    /// lines. Whitespace is collapsed as browsers do, and character references are unescaped.
+    pub fn convert_html(&self, source: &str) -> String {
+        let mut renderer = HtmlRenderer {
+            converter: self,
+            output: String::new(),
+            bold: 0,
+            italic: 0,
+            code: 0,
+            underline: 0,
+            preformatted: 0,
+            unclosed: BTreeMap::new(),
+        };
+        renderer.render(&source.chars().collect::<Vec<_>>());
+        renderer.output.trim_end().into()
+    }
+}
+
+struct HtmlRenderer<'a> {
+    converter: &'a Converter,
+    output: String,
+    bold: usize,
+    italic: usize,
+    code: usize,
+    underline: usize,
+    /// Whitespace is kept as it is in `<pre>`.
+    preformatted: usize,
+    /// Positions for each closer, e.g. `>`, from which the closer is known not to be found.
+    /// Searching again from later positions would scan to the end of the input each time.
+    unclosed: BTreeMap<&'static str, usize>,
+}
+
+impl HtmlRenderer<'_> {
+    fn render(&mut self, chars: &[char]) {
+        let mut pos = 0;
+        while let Some(&c) = chars.get(pos) {
+            match c {
+                '<' => match self.find_tag_end(chars, pos) {
+                    Some(len) => {
+                        let tag = chars[pos + 1..pos + len - 1].iter().collect::<String>();
+                        pos += len;
+                        if let Some(skipped) = self.handle_tag(&tag, chars, pos) {
+                            pos += skipped;
+                        }
+                    }
+                    None => {
+                        self.write_char('<');
+                        pos += 1;
+                    }
+                },
+                '&' => {
+                    let (c, len) = Self::unescape(&chars[pos..]);
+                    self.write_char(c);
+                    pos += len;
+                }
+                c => {
+                    self.write_char(c);
+                    pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Find the length of the tag or comment at `pos`, including `<` and `>`.
+    fn find_tag_end(&mut self, chars: &[char], pos: usize) -> Option<usize> {
+        let rest = &chars[pos..];
+        if rest[1..].starts_with(&['!', '-', '-']) {
+            let end = self.find_closer("-->", chars, pos + 4)?;
+            return Some(end - pos + 3);
+        }
+        if !rest
+            .get(1)
+            .is_some_and(|c| c.is_ascii_alphabetic() || *c == '/' || *c == '!')
+        {
+            return None;
+        }
+        if self.is_unclosed(">", pos + 1) {
+            return None;
+        }
+        // `>` in quoted attribute values does not end the tag.
+        let mut quote = None;
+        let mut last_end = None;
+        for (i, &c) in rest.iter().enumerate().skip(1) {
+            match (quote, c) {
+                (None, '>') => return Some(i + 1),
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), c) if q == c => quote = None,
+                _ => {}
+            }
+            if c == '>' {
+                last_end = Some(i);
+            }
+        }
+        // Tags starting after the last `>` cannot be closed. Ones before it are still searched,
+        // since the `>` may not be quoted for them.
+        let from = pos + last_end.map_or(1, |i| i + 1);
+        self.unclosed.insert(">", from);
+        None
+    }
+
+    /// Find the position of `closer` at or after `start`, e.g. `-->` of a comment.
+    fn find_closer(&mut self, closer: &'static str, chars: &[char], start: usize) -> Option<usize> {
+        if self.is_unclosed(closer, start) {
+            return None;
+        }
+        let found = (start..chars.len()).find(|&i| {
+            chars[i..]
+                .iter()
+                .take(closer.len())
+                .map(char::to_ascii_lowercase)
+                .eq(closer.chars())
+        });
+        if found.is_none() {
+            self.unclosed.insert(closer, start);
+        }
+        found
+    }
+
+    /// Whether `closer` is known not to be found at or after `start`.
+    fn is_unclosed(&self, closer: &str, start: usize) -> bool {
+        self.unclosed.get(closer).is_some_and(|&from| from <= start)
+    }
+
+    /// Apply the tag followed by content at `pos`, and return the length of the content to skip,
+    /// e.g. of `<script>`.
+    fn handle_tag(&mut self, tag: &str, chars: &[char], pos: usize) -> Option<usize> {
+        if tag.starts_with('!') {
+            return None;
+        }
+        let (is_end, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let counter = match name.as_str() {
+            "b" | "strong" => &mut self.bold,
+            "i" | "em" => &mut self.italic,
+            "code" => &mut self.code,
+            "u" => &mut self.underline,
+            "pre" => &mut self.preformatted,
+            "br" => {
+                self.output.push('\n');
+                return None;
+            }
+            "p" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote"
+                if is_end =>
+            {
+                self.new_line();
+                return None;
+            }
+            "script" | "style" if !is_end => {
+                let closing = if name == "script" {
+                    "</script"
+                } else {
+                    "</style"
+                };
+                let end = self.find_closer(closing, chars, pos);
+                return Some(end.unwrap_or(chars.len()) - pos);
+            }
+            _ => return None,
+        };
+        if is_end {
+            *counter = counter.saturating_sub(1);
+        } else {
+            *counter += 1;
+        }
+        None
+    }
+
+    /// Unescape the character reference at the beginning of `chars`, and return the character
+    /// and the length of the reference. `&` is returned as it is if it is not a reference.
+    fn unescape(chars: &[char]) -> (char, usize) {
+        let Some(len) = chars.iter().take(12).position(|&c| c == ';') else {
+            return ('&', 1);
+        };
+        let name = chars[1..len].iter().collect::<String>();
+        let c = match name.as_str() {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{A0}'),
+            _ => name
+                .strip_prefix("#x")
+                .or_else(|| name.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| name.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) => (c, len + 1),
+            None => ('&', 1),
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.output
+            .truncate(self.output.trim_end_matches(' ').len());
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+    }
+
+    fn write_char(&mut self, c: char) {
+        let c = if self.preformatted == 0 && c.is_ascii_whitespace() {
+            // Collapse whitespace into a space, which is omitted at the beginning of lines.
+            if self.output.is_empty() || self.output.ends_with([' ', '\n']) {
+                return;
+            }
+            ' '
+        } else {
+            c
+        };
+
+        let font = if self.code > 0 {
+            Some(Font::Monospace)
+        } else {
+            Emphasis {
+                bold: self.bold > 0,
+                italic: self.italic > 0,
+            }
+            .font()
+        };
+        let decorations = if self.underline > 0 {
+            Decorations::from(Decoration::Underline)
+        } else {
+            Decorations::new()
+        };
+        match font {
+            Some(font) => self.output.extend(self.converter.decorate_chars(
+                self.converter.convert_chars(iter::once(c), font),
+                decorations,
+            )),
+            None => self
+                .output
+                .extend(self.converter.decorate_chars(iter::once(c), decorations)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_tags() {
+        let converter = Converter::new();
+        assert_eq!(
+            "𝐛𝐨𝐥𝐝 𝑖𝑡𝑎𝑙𝑖𝑐 𝒃𝒐𝒕𝒉 𝚌𝚘𝚍𝚎 u\u{332}n\u{332} link",
+            converter.convert_html(
+                "<b>bold</b> <em>italic</em> <strong><i>both</i></strong> <code>code</code> \
+                 <u>un</u> <a href=\"/?a>b\">link</a>"
+            )
+        );
+    }
+
+    #[test]
+    fn blocks_and_whitespace() {
+        let converter = Converter::new();
+        let source = "
+<div>
+  <p>First   <b>line</b></p>
+  <p>a &lt; b &amp;&amp; c&#33; &unknown;</p><!-- <b>comment</b> -->
+  <script>if (a < b) {}</script>
+  line<br>break
+</div>";
+        assert_eq!(
+            "First 𝐥𝐢𝐧𝐞\na < b && c! &unknown;\nline\nbreak",
+            converter.convert_html(source)
+        );
+    }
+
+    #[test]
+    fn unclosed_tags() {
+        let converter = Converter::new();
+        // This takes seconds if unclosed tags are scanned to the end of the input repeatedly.
+        let source = "<a ".repeat(50000);
+        assert_eq!(source.trim_end(), converter.convert_html(&source));
+        let source = "<!--".repeat(20000);
+        assert_eq!(source, converter.convert_html(&source));
+        assert_eq!("x", converter.convert_html("<a title=\"<b\">x"));
+        assert_eq!("a < b", converter.convert_html("a < b<script>x"));
+    }
+}
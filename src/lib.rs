@@ -0,0 +1,47 @@
+//! Decorate alphanumeric characters with various font; special characters in Unicode.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use omekasy::{Converter, Font};
+//!
+//! let converter = Converter::new();
+//! assert_eq!("𝐇𝐞𝐥𝐥𝐨", converter.convert_str("Hello", Font::Bold));
+//! assert_eq!("Hello", converter.revert_str("𝐇𝐞𝐥𝐥𝐨"));
+//! # }
+//! ```
+//!
+//! The core of this crate supports `no_std`. Enable `alloc` feature to use functions returning
+//! `String` or `Vec`, or use iterator-based ones such as `Converter::convert_chars` without it.
+//! `Converter::decompose` needs `decompose` feature, which depends on `unicode-normalization`,
+//! and `Converter::transform` needs `transform` feature, which depends on `unicode-segmentation`.
+//! `Converter::convert_markdown` needs `markdown` feature, which depends on `std` and
+//! `pulldown-cmark`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod convert;
+mod decoration;
+mod fallback;
+mod font;
+#[cfg(feature = "alloc")]
+mod html;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "alloc")]
+mod markup;
+mod style;
+mod transform;
+
+#[cfg(feature = "alloc")]
+pub use crate::convert::DetectedSpan;
+pub use crate::convert::{ConvertChars, Converter};
+pub use crate::decoration::{DecorateChars, Decoration, Decorations};
+#[cfg(feature = "alloc")]
+pub use crate::fallback::ConvertError;
+pub use crate::fallback::{Fallback, ParseFallbackError};
+pub use crate::font::{Font, FontMap, ParseFontError};
+pub use crate::style::{Style, StyleChars};
+pub use crate::transform::{ParseTransformError, Transform};
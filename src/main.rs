@@ -1,16 +1,12 @@
-use clap::Parser;
-use clap::ValueEnum;
-use font::Font;
+use clap::{ArgGroup, Parser};
+use omekasy::{Converter, Decoration, Decorations, Fallback, Font, Style, Transform};
 #[cfg(feature = "crossterm")]
 use prompt::Prompt;
 use std::error::Error;
-use std::io::stdin;
-use std::io::Read;
+use std::fs::{self, File};
+use std::io::{self, stdin, stdout, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
 
-use crate::convert::Converter;
-
-mod convert;
-mod font;
 #[cfg(feature = "crossterm")]
 mod prompt;
 
@@ -18,57 +14,553 @@ mod prompt;
 #[clap(author)]
 #[clap(version)]
 #[clap(about)]
+#[clap(group(
+    ArgGroup::new("mode")
+        .args([
+            "font",
+            "decode",
+            "detect",
+            "markup",
+            "markdown",
+            "html",
+            "transform",
+            "underline",
+            "double_underline",
+            "strike",
+            "overline",
+        ])
+        .multiple(true)
+))]
 /// Decorate latin alphabet and numbers in your input with various font; special characters in
 /// Unicode.
 ///
 /// If you provide neither font type nor input, interactive prompt is displayed.
+///
+/// Output for input given as an argument ends with a newline. Input from stdin or files is
+/// converted line by line, keeping line endings and any other whitespace as they are.
 struct Cli {
     #[clap(short, long, value_enum)]
     font: Option<Font>,
+    /// Convert decorated characters in the input back to plain ones.
+    #[clap(short, long, conflicts_with = "font")]
+    decode: bool,
+    /// Detect fonts which the input is decorated with.
+    #[clap(long, conflicts_with_all = ["font", "decode"])]
+    detect: bool,
+    /// Convert only parts marked up like `**bold**`, `*italic*`, `` `monospace` `` or
+    /// `{script:text}`.
+    #[clap(short, long, conflicts_with_all = ["font", "decode", "detect"])]
+    markup: bool,
+    /// Render Markdown as plain text, converting emphasis, code and headings to fonts.
+    #[clap(long, conflicts_with_all = ["font", "decode", "detect", "markup"])]
+    markdown: bool,
+    /// Render HTML as plain text, converting `<b>`, `<i>`, `<code>` and `<u>` to fonts and
+    /// underlines, and stripping other tags.
+    #[clap(long, conflicts_with_all = ["font", "decode", "detect", "markup", "markdown"])]
+    html: bool,
+    /// Transform the whole input, i.e. turn it upside down or mirror it.
+    #[clap(
+        long,
+        value_enum,
+        conflicts_with_all = ["font", "decode", "detect", "markup", "markdown", "html"]
+    )]
+    transform: Option<Transform>,
+    /// Decompose accented letters so that their base letters are converted to the font.
+    #[clap(long, requires = "font")]
+    decompose: bool,
+    /// How to handle characters which the font does not have: keep, drop, replace=<CHAR>, error
+    /// or nearest, which uses a similar font. With error, input from stdin or files is checked
+    /// as a whole before anything is written.
+    #[clap(long, value_name = "POLICY", default_value_t, requires = "font")]
+    fallback: Fallback,
+    /// Underline the output with combining characters.
+    #[clap(long, conflicts_with_all = ["decode", "detect"])]
+    underline: bool,
+    /// Underline the output with double lines.
+    #[clap(long, conflicts_with_all = ["decode", "detect"])]
+    double_underline: bool,
+    /// Strike through the output.
+    #[clap(long, visible_alias = "strikethrough", conflicts_with_all = ["decode", "detect"])]
+    strike: bool,
+    /// Draw a line over the output.
+    #[clap(long, conflicts_with_all = ["decode", "detect"])]
+    overline: bool,
+    /// Do not output the trailing newline.
+    /// For input from stdin or files, the line ending of the last line is omitted.
+    #[clap(short = 'n', long)]
+    no_newline: bool,
+    /// Read input from the file instead of the argument or stdin. Can be given multiple times.
+    #[clap(
+        short,
+        long,
+        value_name = "PATH",
+        conflicts_with = "input",
+        requires = "mode"
+    )]
+    input_file: Vec<PathBuf>,
+    /// Overwrite input files with the output instead of printing it.
+    #[clap(long, requires = "input_file", conflicts_with = "detect")]
+    in_place: bool,
+    /// Back up input files to the path with this suffix before overwriting them.
+    #[clap(long, value_name = "SUFFIX", requires = "in_place")]
+    backup: Option<String>,
     input: Option<String>,
 }
 
+impl Cli {
+    fn decorations(&self) -> Decorations {
+        [
+            (self.underline, Decoration::Underline),
+            (self.double_underline, Decoration::DoubleUnderline),
+            (self.strike, Decoration::Strikethrough),
+            (self.overline, Decoration::Overline),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, decoration)| enabled.then_some(decoration))
+        .collect()
+    }
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let cli: Cli = Cli::parse();
+    let converter = Converter::new();
+
+    if cli.detect {
+        let input = if !cli.input_file.is_empty() {
+            let mut input = String::new();
+            open_files(&cli.input_file)?.read_to_string(&mut input)?;
+            input
+        } else if let Some(input) = cli.input {
+            input
+        } else {
+            let mut input = String::new();
+            stdin().read_to_string(&mut input)?;
+            input
+        };
+        let spans = converter.detect_str(input.trim_end());
+        let mut lines = Vec::new();
+        for span in &spans {
+            let fonts = if span.fonts.is_empty() {
+                "plain".to_string()
+            } else {
+                span.fonts
+                    .iter()
+                    .map(Font::name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            // Show which part of the input each font applies to only if the input is mixed.
+            if spans.len() == 1 {
+                lines.push(fonts);
+            } else {
+                lines.push(format!("{fonts}: {}", span.text));
+            }
+        }
+        print_output(&lines.join("\n"), cli.no_newline);
+        return Ok(());
+    }
 
-    match (cli.input, cli.font) {
-        (Some(input), Some(font)) => {
-            let converter = Converter::new(&[font]);
-            print!(
-                "{}",
-                converter.convert(&input.chars().collect::<Vec<_>>(), font)
-            );
+    let style = Style {
+        font: cli.font,
+        decorations: cli.decorations(),
+        fallback: cli.fallback,
+    };
+    let convert = |source: &str| {
+        let converted = if cli.decode {
+            converter.revert_str(source)
+        } else if cli.markup {
+            converter.convert_markup(source)
+        } else if cli.markdown {
+            converter.convert_markdown(source)
+        } else if cli.html {
+            converter.convert_html(source)
+        } else if let Some(transform) = cli.transform {
+            converter.transform_str(source, transform)
+        } else {
+            let decomposed;
+            let source = match cli.font {
+                Some(font) if cli.decompose => {
+                    decomposed = converter.decompose_str(source, font);
+                    &decomposed
+                }
+                _ => source,
+            };
+            return converter.try_stylize_str(source, style).map_err(|e| {
+                let mut message = e.to_string();
+                // Accented letters can be decorated by decomposing them.
+                let is_decomposable = |&c: &char| {
+                    converter.decompose_str(c.encode_utf8(&mut [0; 4]), e.font()) != c.to_string()
+                };
+                if !cli.decompose && e.characters().iter().any(is_decomposable) {
+                    message.push_str(" (use --decompose to decorate accented letters)");
+                }
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            });
+        };
+        Ok(converter.decorate_str(&converted, style.decorations))
+    };
+    // Markdown and HTML cannot be converted line by line since elements span multiple lines.
+    // Upside-down text reverses the order of lines as well, keeping line endings in place.
+    // With `--fallback error`, all characters which cannot be converted are reported at once.
+    let output = Output {
+        by_line: !cli.markdown && !cli.html && cli.transform != Some(Transform::UpsideDown),
+        keeps_endings: cli.transform == Some(Transform::UpsideDown),
+        checks_whole: cli.fallback == Fallback::Error,
+        no_newline: cli.no_newline,
+    };
+
+    match (
+        cli.input,
+        style != Style::default()
+            || cli.decode
+            || cli.markup
+            || cli.markdown
+            || cli.html
+            || cli.transform.is_some(),
+    ) {
+        (None, _) if cli.in_place => {
+            for path in &cli.input_file {
+                convert_in_place(path, cli.backup.as_deref(), convert, output)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+            }
+        }
+        (None, _) if !cli.input_file.is_empty() => {
+            let reader = BufReader::new(open_files(&cli.input_file)?);
+            output
+                .convert(reader, &mut stdout().lock(), convert)
+                .map_err(|e| e.to_string())?;
+        }
+        (Some(input), true) => {
+            let converted = convert(&input).map_err(|e| e.to_string())?;
+            print_output(&converted, cli.no_newline);
         }
         #[cfg(feature = "crossterm")]
-        (None, None) => {
-            let mut prompt = Prompt::new(Font::value_variants());
+        (None, false) => {
+            let mut prompt = Prompt::new(Font::ALL);
             prompt.start_prompt()?;
         }
         #[cfg(not(feature = "crossterm"))]
-        (None, None) => {
+        (None, false) => {
             return Err("Compiled without terminal support. Please specify the font as a command line parameter".into());
         }
-        (Some(input), None) => {
-            let fonts = Font::value_variants();
-            let converter = Converter::new(fonts);
-            for &font in fonts {
-                println!(
-                    "{}",
-                    converter.convert(&input.chars().collect::<Vec<_>>(), font)
-                );
-            }
+        (Some(input), false) => {
+            let outputs = Font::ALL
+                .iter()
+                .map(|&font| converter.convert_str(&input, font))
+                .collect::<Vec<_>>();
+            print_output(&outputs.join("\n"), cli.no_newline);
         }
-        (None, Some(font)) => {
-            let converter = Converter::new(&[font]);
-            let mut input = String::new();
-            stdin().read_to_string(&mut input)?;
-            let input = input.trim_end();
-            print!(
-                "{}",
-                converter.convert(&input.chars().collect::<Vec<_>>(), font)
-            );
+        (None, true) => {
+            output
+                .convert(stdin().lock(), &mut stdout().lock(), convert)
+                .map_err(|e| e.to_string())?;
         }
     }
 
     Ok(())
 }
+
+/// Print output for input given as an argument.
+fn print_output(output: &str, no_newline: bool) {
+    if no_newline {
+        print!("{output}");
+    } else {
+        println!("{output}");
+    }
+}
+
+/// Open files and concatenate them into a reader.
+fn open_files(paths: &[PathBuf]) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    let mut reader: Box<dyn Read> = Box::new(io::empty());
+    for path in paths {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        reader = Box::new(reader.chain(file));
+    }
+    Ok(reader)
+}
+
+/// Convert the file and overwrite it with the output.
+/// If `backup` is given, the original file is copied to the path with the suffix beforehand.
+/// The output is written to a temporary file which then replaces the original one, so that the
+/// original file is not lost even if writing fails.
+fn convert_in_place<F>(
+    path: &Path,
+    backup: Option<&str>,
+    convert: F,
+    output: Output,
+) -> io::Result<()>
+where
+    F: Fn(&str) -> io::Result<String>,
+{
+    let input = fs::read(path)?;
+    let mut converted = Vec::new();
+    output.convert(input.as_slice(), &mut converted, convert)?;
+
+    if let Some(suffix) = backup {
+        let mut backup_path = path.as_os_str().to_owned();
+        backup_path.push(suffix);
+        fs::copy(path, backup_path)?;
+    }
+
+    // The temporary file is created in the same directory, since renaming across file systems
+    // fails.
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(format!(".omekasy-{}", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+    let result = fs::write(&temp_path, converted)
+        .and_then(|()| fs::set_permissions(&temp_path, fs::metadata(path)?.permissions()))
+        .and_then(|()| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// How to convert input from stdin or files.
+#[derive(Debug, Clone, Copy)]
+struct Output {
+    /// Convert input line by line so that endless or huge input can be processed.
+    /// Otherwise, the whole input is converted at once and followed by a newline.
+    by_line: bool,
+    /// The conversion keeps line endings of the input, so the whole output is written as it is
+    /// instead of being followed by a newline.
+    keeps_endings: bool,
+    /// Convert the whole input once before writing anything, so that nothing is written and all
+    /// errors are reported at once if some lines cannot be converted.
+    checks_whole: bool,
+    no_newline: bool,
+}
+
+impl Output {
+    fn convert<R, W, F>(&self, reader: R, writer: &mut W, convert: F) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: Fn(&str) -> io::Result<String>,
+    {
+        let result = if self.checks_whole {
+            self.convert_checked(reader, writer, convert)
+        } else if self.by_line {
+            self.convert_lines(reader, writer, convert)
+        } else {
+            self.convert_whole(reader, writer, convert)
+        };
+        match result {
+            // The reader of the output has gone away, e.g. `omekasy -f bold | head`.
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        }
+    }
+
+    /// The output is the same as without checking, but written only after the whole input is
+    /// converted successfully.
+    fn convert_checked<R, W, F>(&self, mut reader: R, writer: &mut W, convert: F) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: Fn(&str) -> io::Result<String>,
+    {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        convert(&input)?;
+        let mut converted = Vec::new();
+        let output = Output {
+            checks_whole: false,
+            ..*self
+        };
+        output.convert(input.as_bytes(), &mut converted, convert)?;
+        writer.write_all(&converted)?;
+        writer.flush()
+    }
+
+    /// Line endings are written as they are, and each line is flushed as soon as it is
+    /// converted.
+    fn convert_lines<R, W, F>(&self, mut reader: R, writer: &mut W, convert: F) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: Fn(&str) -> io::Result<String>,
+    {
+        let mut line = String::new();
+        // With `--no-newline`, a line ending is written only after the next line is read.
+        let mut pending_ending = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            let content = line.trim_end_matches(['\n', '\r']);
+            let ending = &line[content.len()..];
+            if self.no_newline {
+                write!(writer, "{}{}", pending_ending, convert(content)?)?;
+                pending_ending = ending.to_string();
+            } else {
+                write!(writer, "{}{}", convert(content)?, ending)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    fn convert_whole<R, W, F>(&self, mut reader: R, writer: &mut W, convert: F) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: Fn(&str) -> io::Result<String>,
+    {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        let converted = convert(&input)?;
+        if !self.keeps_endings {
+            write!(writer, "{converted}")?;
+            if !self.no_newline {
+                writeln!(writer)?;
+            }
+        } else if self.no_newline {
+            // Only the last line ending is removed as in `Output::convert_lines`.
+            let trimmed = converted
+                .strip_suffix('\n')
+                .map_or(converted.as_str(), |rest| {
+                    rest.strip_suffix('\r').unwrap_or(rest)
+                });
+            write!(writer, "{trimmed}")?;
+        } else {
+            write!(writer, "{converted}")?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upper(source: &str) -> io::Result<String> {
+        Ok(source.to_uppercase())
+    }
+
+    fn convert(output: Output, input: &str) -> String {
+        let mut writer = Vec::new();
+        output
+            .convert(input.as_bytes(), &mut writer, upper)
+            .unwrap();
+        String::from_utf8(writer).unwrap()
+    }
+
+    #[test]
+    fn convert_lines() {
+        let output = Output {
+            by_line: true,
+            keeps_endings: false,
+            checks_whole: false,
+            no_newline: false,
+        };
+        assert_eq!("AB\r\nCD\n", convert(output, "ab\r\ncd\n"));
+        assert_eq!("AB\n\nCD", convert(output, "ab\n\ncd"));
+        assert_eq!(" A \t\n", convert(output, " a \t\n"));
+        assert_eq!("", convert(output, ""));
+    }
+
+    #[test]
+    fn convert_lines_no_newline() {
+        let output = Output {
+            by_line: true,
+            keeps_endings: false,
+            checks_whole: false,
+            no_newline: true,
+        };
+        assert_eq!("AB\r\nCD", convert(output, "ab\r\ncd\r\n"));
+        assert_eq!("AB\n\nCD", convert(output, "ab\n\ncd\n"));
+        assert_eq!("AB", convert(output, "ab"));
+    }
+
+    #[test]
+    fn convert_whole() {
+        let output = Output {
+            by_line: false,
+            keeps_endings: false,
+            checks_whole: false,
+            no_newline: false,
+        };
+        assert_eq!("AB\nCD\n", convert(output, "ab\ncd"));
+        let output = Output {
+            by_line: false,
+            keeps_endings: false,
+            checks_whole: false,
+            no_newline: true,
+        };
+        assert_eq!("AB\nCD", convert(output, "ab\ncd"));
+    }
+
+    #[test]
+    fn convert_whole_keeping_endings() {
+        let output = Output {
+            by_line: false,
+            keeps_endings: true,
+            checks_whole: false,
+            no_newline: false,
+        };
+        assert_eq!("AB\nCD\n", convert(output, "ab\ncd\n"));
+        assert_eq!("AB\nCD", convert(output, "ab\ncd"));
+        let output = Output {
+            by_line: false,
+            keeps_endings: true,
+            checks_whole: false,
+            no_newline: true,
+        };
+        assert_eq!("AB\nCD", convert(output, "ab\ncd\r\n"));
+        assert_eq!("AB\nCD", convert(output, "ab\ncd"));
+    }
+
+    #[test]
+    fn convert_checked() {
+        let output = Output {
+            by_line: true,
+            keeps_endings: false,
+            checks_whole: true,
+            no_newline: false,
+        };
+        let fail_on_digits = |source: &str| {
+            if source.contains(|c: char| c.is_ascii_digit()) {
+                Err(io::Error::new(io::ErrorKind::InvalidData, "digits"))
+            } else {
+                Ok(format!("[{source}]"))
+            }
+        };
+        let mut writer = Vec::new();
+        assert!(output
+            .convert("ab\ncd\n1\n".as_bytes(), &mut writer, fail_on_digits)
+            .is_err());
+        assert!(writer.is_empty());
+        // The input is converted line by line after checking.
+        output
+            .convert("ab\ncd\n".as_bytes(), &mut writer, fail_on_digits)
+            .unwrap();
+        assert_eq!(b"[ab]\n[cd]\n", writer.as_slice());
+    }
+
+    #[test]
+    fn in_place_with_backup() {
+        let dir = std::env::temp_dir().join(format!("omekasy-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notes.txt");
+        fs::write(&path, "ab\r\ncd").unwrap();
+        let output = Output {
+            by_line: true,
+            keeps_endings: false,
+            checks_whole: false,
+            no_newline: false,
+        };
+
+        convert_in_place(&path, Some(".orig"), upper, output).unwrap();
+        assert_eq!("AB\r\nCD", fs::read_to_string(&path).unwrap());
+        assert_eq!(
+            "ab\r\ncd",
+            fs::read_to_string(dir.join("notes.txt.orig")).unwrap()
+        );
+        // No temporary file is left.
+        assert_eq!(2, fs::read_dir(&dir).unwrap().count());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
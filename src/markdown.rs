@@ -0,0 +1,262 @@
+use pulldown_cmark::{Event, LinkType, Parser, Tag, TagEnd};
+
+use crate::convert::Converter;
+use crate::font::Font;
+use crate::markup::Emphasis;
+
+impl Converter {
+    /// Render CommonMark text as plain text decorated with fonts, for platforms which do not
+    /// support Markdown.
+    ///
+    /// Emphasis, strong emphasis and both are converted to `Font::Italic`, `Font::Bold` and
+    /// `Font::BoldItalic` respectively, inline code and code blocks to `Font::Monospace`, and
+    /// headings to `Font::BoldSans`. Markers of lists are replaced with bullets or numbers, and
+    /// links are followed by their destination. HTML blocks are rendered with
+    /// `Converter::convert_html`, and inline HTML tags are stripped.
+    pub fn convert_markdown(&self, source: &str) -> String {
+        let mut renderer = MarkdownRenderer {
+            converter: self,
+            output: String::new(),
+            strong: 0,
+            emphasis: 0,
+            heading: false,
+            code_block: false,
+            lists: Vec::new(),
+            links: Vec::new(),
+            html: None,
+            pending_newlines: 0,
+        };
+        for event in Parser::new(source) {
+            renderer.render(event);
+        }
+        renderer.output
+    }
+}
+
+struct MarkdownRenderer<'a> {
+    converter: &'a Converter,
+    output: String,
+    strong: usize,
+    emphasis: usize,
+    heading: bool,
+    code_block: bool,
+    /// Number of the next item of each nested list, or `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    /// Destinations of links being rendered, or `None` if the text itself is the destination.
+    links: Vec<Option<String>>,
+    /// Source of the HTML block being rendered.
+    html: Option<String>,
+    /// Newlines to write before the next text to separate blocks.
+    pending_newlines: usize,
+}
+
+impl MarkdownRenderer<'_> {
+    fn render(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.write_text(&text, self.font()),
+            Event::Code(code) => self.write_text(&code, Some(Font::Monospace)),
+            Event::Html(html) => self.html.get_or_insert_with(String::new).push_str(&html),
+            // Other inline HTML is stripped, since the text inside tags is rendered anyway.
+            Event::InlineHtml(html) if is_line_break(&html) => self.write("\n"),
+            Event::SoftBreak | Event::HardBreak => self.write("\n"),
+            Event::Rule => {
+                self.write("---");
+                self.end_block();
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { .. } => self.heading = true,
+            Tag::CodeBlock(_) => self.code_block = true,
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::List(first) => {
+                // Nested lists start on the next line of the parent item.
+                if !self.lists.is_empty() {
+                    self.pending_newlines = self.pending_newlines.max(1);
+                }
+                self.lists.push(first);
+            }
+            Tag::Item => {
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.write(&format!("{indent}{marker}"));
+            }
+            Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            } => {
+                let dest = match link_type {
+                    LinkType::Autolink | LinkType::Email => None,
+                    _ => Some(dest_url.into_string()),
+                };
+                self.links.push(dest);
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::BlockQuote(_) => self.end_block(),
+            TagEnd::HtmlBlock => {
+                let html = self.html.take().unwrap_or_default();
+                let rendered = self.converter.convert_html(&html);
+                // Blocks such as comments are rendered to nothing and not separated.
+                if !rendered.is_empty() {
+                    self.write(&rendered);
+                    self.end_block();
+                }
+            }
+            TagEnd::Heading(_) => {
+                self.heading = false;
+                self.end_block();
+            }
+            TagEnd::CodeBlock => {
+                self.code_block = false;
+                self.output
+                    .truncate(self.output.trim_end_matches('\n').len());
+                self.end_block();
+            }
+            TagEnd::Emphasis => self.emphasis -= 1,
+            TagEnd::Strong => self.strong -= 1,
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.end_block();
+            }
+            TagEnd::Item => self.pending_newlines = self.pending_newlines.max(1),
+            TagEnd::Link => {
+                if let Some(Some(dest)) = self.links.pop() {
+                    self.write(&format!(" ({dest})"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn font(&self) -> Option<Font> {
+        if self.code_block {
+            Some(Font::Monospace)
+        } else if self.heading {
+            if self.emphasis > 0 {
+                Some(Font::BoldItalicSans)
+            } else {
+                Some(Font::BoldSans)
+            }
+        } else {
+            Emphasis {
+                bold: self.strong > 0,
+                italic: self.emphasis > 0,
+            }
+            .font()
+        }
+    }
+
+    /// Separate the next block with a blank line, or a newline in lists.
+    fn end_block(&mut self) {
+        let newlines = if self.lists.is_empty() { 2 } else { 1 };
+        self.pending_newlines = self.pending_newlines.max(newlines);
+    }
+
+    fn write(&mut self, text: &str) {
+        if !self.output.is_empty() {
+            for _ in 0..self.pending_newlines {
+                self.output.push('\n');
+            }
+        }
+        self.pending_newlines = 0;
+        self.output.push_str(text);
+    }
+
+    fn write_text(&mut self, text: &str, font: Option<Font>) {
+        match font {
+            Some(font) => self.write(&self.converter.convert_str(text, font)),
+            None => self.write(text),
+        }
+    }
+}
+
+/// Whether the inline HTML is a `<br>` tag.
+fn is_line_break(html: &str) -> bool {
+    let Some(tag) = html.strip_prefix('<') else {
+        return false;
+    };
+    tag.get(..2)
+        .is_some_and(|name| name.eq_ignore_ascii_case("br"))
+        && !tag[2..].starts_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inlines() {
+        let converter = Converter::new();
+        assert_eq!(
+            "𝑒𝑚, 𝐬𝐭𝐫𝐨𝐧𝐠, 𝒃𝒐𝒕𝒉 and 𝚌𝚘𝚍𝚎",
+            converter.convert_markdown("*em*, __strong__, ***both*** and `code`")
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let converter = Converter::new();
+        let source = "\
+# Title *here*
+
+Some text
+with [link](https://example.com).
+
+- one
+- two
+  1. nested
+  2. list
+
+```
+let a;
+```
+";
+        assert_eq!(
+            "\
+𝗧𝗶𝘁𝗹𝗲 𝙝𝙚𝙧𝙚
+
+Some text
+with link (https://example.com).
+
+• one
+• two
+  1. nested
+  2. list
+
+𝚕𝚎𝚝 𝚊;",
+            converter.convert_markdown(source)
+        );
+    }
+
+    #[test]
+    fn html() {
+        let converter = Converter::new();
+        assert_eq!("Hi x\ny", converter.convert_markdown("Hi <b>x</b><br/>y"));
+        assert_eq!(
+            "𝐁𝐨𝐥𝐝 text\n\nafter",
+            converter.convert_markdown("<p><b>Bold</b> text</p>\n\n<!-- note -->\n\nafter")
+        );
+        assert_eq!(
+            "𝐁𝐨𝐥𝐝",
+            converter.convert_markdown("<div>\n<b>Bold</b>\n</div>\n")
+        );
+    }
+}
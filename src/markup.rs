@@ -0,0 +1,292 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter;
+
+use crate::convert::Converter;
+use crate::font::Font;
+
+impl Converter {
+    /// Convert only the parts of given text marked up with inline syntax below.
+    /// Other characters remain unchanged, and markers which are not closed within the line are
+    /// left as they are.
+    ///
+    /// - `**bold**`, `*italic*` and `***bold italic***`
+    /// - `` `monospace` ``
+    /// - `{font:text}` for any font, e.g. `{bold-script:text}`
+    /// - `\*` to write a marker character literally
+    pub fn convert_markup(&self, source: &str) -> String {
+        let chars = source.chars().collect::<Vec<_>>();
+        let mut parser = MarkupParser {
+            converter: self,
+            chars: &chars,
+            pos: 0,
+            failed: BTreeSet::new(),
+            unclosed: BTreeMap::new(),
+        };
+        let mut output = String::new();
+        parser.parse(None, Emphasis::default(), &mut output);
+        output
+    }
+}
+
+/// Emphasis which `*` and `**` apply.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Emphasis {
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+}
+
+impl Emphasis {
+    pub(crate) fn font(&self) -> Option<Font> {
+        match (self.bold, self.italic) {
+            (true, true) => Some(Font::BoldItalic),
+            (true, false) => Some(Font::Bold),
+            (false, true) => Some(Font::Italic),
+            (false, false) => None,
+        }
+    }
+}
+
+struct MarkupParser<'a> {
+    converter: &'a Converter,
+    chars: &'a [char],
+    pos: usize,
+    /// States from which the closer is known not to be found in the line.
+    failed: BTreeSet<ParseState>,
+    /// Range of positions for each closer of font spans, from which the closer is known not to
+    /// be found in the line.
+    unclosed: BTreeMap<char, (usize, usize)>,
+}
+
+/// Position with the closer and emphasis being parsed, which determine the rest of parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct ParseState {
+    pos: usize,
+    closer_len: usize,
+    bold: bool,
+    italic: bool,
+}
+
+impl MarkupParser<'_> {
+    /// Parse characters until `closer` and write converted ones to `output`.
+    /// Returns `false` if `closer` is not found in the line, in which case `output` is incomplete.
+    fn parse(&mut self, closer: Option<&str>, emphasis: Emphasis, output: &mut String) -> bool {
+        let mut visited = Vec::new();
+        let found = self.parse_inner(closer, emphasis, output, &mut visited);
+        if !found {
+            // Parsing from the same state always fails again, so remember the states to avoid
+            // scanning unclosed markers to the end of the line repeatedly.
+            self.failed.extend(visited);
+        }
+        found
+    }
+
+    /// Body of `MarkupParser::parse`, which collects states where parsing resumes to `visited`.
+    fn parse_inner(
+        &mut self,
+        closer: Option<&str>,
+        emphasis: Emphasis,
+        output: &mut String,
+        visited: &mut Vec<ParseState>,
+    ) -> bool {
+        while let Some(&c) = self.chars.get(self.pos) {
+            if let Some(closer) = closer {
+                let state = ParseState {
+                    pos: self.pos,
+                    closer_len: closer.len(),
+                    bold: emphasis.bold,
+                    italic: emphasis.italic,
+                };
+                if c == '\n' || self.failed.contains(&state) {
+                    return false;
+                }
+                visited.push(state);
+            }
+            if self.starts_with("**") && !emphasis.bold {
+                let inner = Emphasis {
+                    bold: true,
+                    ..emphasis
+                };
+                if self.try_enclosed("**", inner, output) {
+                    continue;
+                }
+            }
+            // Like Markdown, closing markers must follow non-whitespace characters.
+            let follows_text = self.pos > 0 && !self.chars[self.pos - 1].is_whitespace();
+            if let Some(closer) = closer.filter(|&closer| follows_text && self.starts_with(closer))
+            {
+                self.pos += closer.chars().count();
+                return true;
+            }
+            if c == '*' && !emphasis.italic {
+                let inner = Emphasis {
+                    italic: true,
+                    ..emphasis
+                };
+                if self.try_enclosed("*", inner, output) {
+                    continue;
+                }
+            }
+            if c == '`' && self.try_font_span('`', Font::Monospace, output) {
+                continue;
+            }
+            if c == '{' && self.try_font_name(output) {
+                continue;
+            }
+
+            let c = match (c, self.chars.get(self.pos + 1)) {
+                ('\\', Some(&escaped)) if "*`{\\".contains(escaped) => {
+                    self.pos += 1;
+                    escaped
+                }
+                _ => c,
+            };
+            self.push(c, emphasis.font(), output);
+            self.pos += 1;
+        }
+
+        closer.is_none()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        let mut chars = self.chars[self.pos..].iter();
+        s.chars().all(|c| chars.next() == Some(&c))
+    }
+
+    /// Parse text enclosed by `marker` with `emphasis`.
+    /// If the closing marker is not found, nothing is consumed.
+    fn try_enclosed(&mut self, marker: &str, emphasis: Emphasis, output: &mut String) -> bool {
+        let start = self.pos;
+        self.pos += marker.chars().count();
+        // Opening markers must be followed by non-whitespace characters.
+        if self.chars.get(self.pos).is_none_or(|c| c.is_whitespace()) {
+            self.pos = start;
+            return false;
+        }
+        let content_start = self.pos;
+        let mut inner = String::new();
+        if self.parse(Some(marker), emphasis, &mut inner)
+            && self.pos > content_start + marker.chars().count()
+        {
+            output.push_str(&inner);
+            true
+        } else {
+            self.pos = start;
+            false
+        }
+    }
+
+    /// Convert text until `closer` to `font` without interpreting markup in it.
+    fn try_font_span(&mut self, closer: char, font: Font, output: &mut String) -> bool {
+        if let Some(&(start, end)) = self.unclosed.get(&closer) {
+            if (start..end).contains(&self.pos) {
+                return false;
+            }
+        }
+        let rest = &self.chars[self.pos + 1..];
+        let Some(len) = rest
+            .iter()
+            .take_while(|&&c| c != '\n')
+            .position(|&c| c == closer)
+        else {
+            // The closer is not found from any later position in the line either.
+            let line_len = rest.iter().take_while(|&&c| c != '\n').count();
+            self.unclosed
+                .insert(closer, (self.pos, self.pos + 1 + line_len));
+            return false;
+        };
+        output.extend(
+            self.converter
+                .convert_chars(rest[..len].iter().copied(), font),
+        );
+        self.pos += len + 2;
+        true
+    }
+
+    /// Convert `{font:text}` to the font.
+    fn try_font_name(&mut self, output: &mut String) -> bool {
+        let rest = &self.chars[self.pos + 1..];
+        // Font names are short, so the colon is searched for only in a few characters.
+        let max_len = Font::ALL.iter().map(|font| font.name().len()).max();
+        let Some(colon) = rest
+            .iter()
+            .take(max_len.unwrap_or_default() + 1)
+            .take_while(|&&c| c != '}' && c != '\n')
+            .position(|&c| c == ':')
+        else {
+            return false;
+        };
+        let Ok(font) = rest[..colon].iter().collect::<String>().parse::<Font>() else {
+            return false;
+        };
+        self.pos += colon + 1;
+        if self.try_font_span('}', font, output) {
+            true
+        } else {
+            self.pos -= colon + 1;
+            false
+        }
+    }
+
+    fn push(&self, c: char, font: Option<Font>, output: &mut String) {
+        match font {
+            Some(font) => output.extend(self.converter.convert_chars(iter::once(c), font)),
+            None => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn emphasis() {
+        let converter = Converter::new();
+        assert_eq!(
+            "𝐛𝐨𝐥𝐝, 𝑖𝑡𝑎𝑙𝑖𝑐 and 𝒃𝒐𝒕𝒉",
+            converter.convert_markup("**bold**, *italic* and ***both***")
+        );
+        assert_eq!(
+            "𝑖𝑡𝑎𝑙𝑖𝑐 𝒂𝒏𝒅 𝒃𝒐𝒕𝒉",
+            converter.convert_markup("*italic **and both***")
+        );
+    }
+
+    #[test]
+    fn font_spans() {
+        let converter = Converter::new();
+        assert_eq!(
+            "𝚌𝚘𝚍𝚎 and 𝓈𝒸𝓇𝒾𝓅𝓉 *",
+            converter.convert_markup("`code` and {script:script} *")
+        );
+        assert_eq!(
+            "{unknown:font} `a",
+            converter.convert_markup("{unknown:font} `a")
+        );
+    }
+
+    #[test]
+    fn unclosed_markers() {
+        let converter = Converter::new();
+        assert_eq!("*𝑎", converter.convert_markup("**a*"));
+        assert_eq!("2 * 3 * 4", converter.convert_markup("2 * 3 * 4"));
+        assert_eq!("*a\nb*", converter.convert_markup("*a\nb*"));
+        assert_eq!("*𝐚 \\", converter.convert_markup("\\***a** \\"));
+    }
+
+    #[test]
+    fn many_unclosed_markers() {
+        let converter = Converter::new();
+        // This takes seconds if unclosed markers are scanned to the end of the line repeatedly.
+        let source = "**a *b ".repeat(3000);
+        let expected = format!("*𝑎 *𝑏 {}*a *b ", "*a 𝑏 ".repeat(2998));
+        assert_eq!(expected, converter.convert_markup(&source));
+        let source = "{".repeat(50000);
+        assert_eq!(source, converter.convert_markup(&source));
+        let source = "{bold:a b ".repeat(20000);
+        assert_eq!(source, converter.convert_markup(&source));
+    }
+}
@@ -3,8 +3,7 @@ use std::{
     time::Duration,
 };
 
-use crate::convert::Converter;
-use crate::font::Font;
+use omekasy::{Converter, Font};
 
 use crossterm::{
     cursor::{MoveLeft, MoveRight, MoveToNextLine, MoveToPreviousLine},
@@ -34,7 +33,7 @@ impl Prompt {
     const PROMPT_SYMBOL: &'static str = "> ";
 
     pub fn new(fonts: &'static [Font]) -> Self {
-        let converter = Converter::new(fonts);
+        let converter = Converter::new();
         let num_whole_lines = fonts.len() + 1;
 
         Self {
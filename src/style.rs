@@ -0,0 +1,121 @@
+use crate::convert::{ConvertChars, Converter};
+use crate::decoration::{DecorateChars, Decoration, Decorations};
+use crate::fallback::Fallback;
+use crate::font::Font;
+
+/// Font combined with decorations.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    /// Font to convert characters to, or `None` to keep them as they are.
+    pub font: Option<Font>,
+    pub decorations: Decorations,
+    /// How to handle characters which the font does not have.
+    pub fallback: Fallback,
+}
+
+impl Style {
+    pub fn new(font: Option<Font>) -> Self {
+        Self {
+            font,
+            decorations: Decorations::new(),
+            fallback: Fallback::Keep,
+        }
+    }
+
+    /// Add the decoration to the style.
+    pub fn with(mut self, decoration: Decoration) -> Self {
+        self.decorations.insert(decoration);
+        self
+    }
+
+    /// Set the fallback policy for characters which the font does not have.
+    pub fn with_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+}
+
+impl From<Font> for Style {
+    fn from(font: Font) -> Self {
+        Self::new(Some(font))
+    }
+}
+
+impl Converter {
+    /// Convert given characters to the font of the style, then decorate them.
+    #[cfg(feature = "alloc")]
+    pub fn stylize(&self, source: &[char], style: Style) -> alloc::string::String {
+        self.stylize_chars(source.iter().copied(), style).collect()
+    }
+
+    /// Same as `Converter::stylize`, but takes a string slice.
+    #[cfg(feature = "alloc")]
+    pub fn stylize_str(&self, source: &str, style: Style) -> alloc::string::String {
+        self.stylize_chars(source.chars(), style).collect()
+    }
+
+    /// Same as `Converter::stylize`, but lazily yields characters without allocation.
+    pub fn stylize_chars<I>(&self, source: I, style: Style) -> StyleChars<I::IntoIter>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let source = match style.font {
+            Some(font) => {
+                StyleSource::Converted(self.convert_chars_with(source, font, style.fallback))
+            }
+            None => StyleSource::Plain(source.into_iter()),
+        };
+        StyleChars(self.decorate_chars(source, style.decorations))
+    }
+}
+
+/// Iterator returned by `Converter::stylize_chars`.
+#[derive(Debug, Clone)]
+pub struct StyleChars<I: Iterator<Item = char>>(DecorateChars<StyleSource<I>>);
+
+impl<I: Iterator<Item = char>> Iterator for StyleChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum StyleSource<I: Iterator<Item = char>> {
+    Converted(ConvertChars<I>),
+    Plain(I),
+}
+
+impl<I: Iterator<Item = char>> Iterator for StyleSource<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            StyleSource::Converted(converted) => converted.next(),
+            StyleSource::Plain(plain) => plain.next(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stylize() {
+        let converter = Converter::new();
+        let style = Style::from(Font::BoldSans)
+            .with(Decoration::Strikethrough)
+            .with(Decoration::Underline);
+        assert_eq!(
+            "𝗮\u{332}\u{336}𝗯\u{332}\u{336}",
+            converter.stylize_str("ab", style)
+        );
+        assert_eq!("ab", converter.stylize_str("ab", Style::default()));
+        let style = Style::from(Font::Script)
+            .with(Decoration::Underline)
+            .with_fallback(Fallback::Drop);
+        assert_eq!("𝒶\u{332} \u{332}", converter.stylize_str("a 1", style));
+    }
+}
@@ -0,0 +1,321 @@
+#[cfg(feature = "transform")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "transform")]
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "transform")]
+use crate::convert::{Converter, Normalize};
+
+/// Transformation of whole text, unlike fonts which convert each character independently.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Transform {
+    /// Rotate text by 180 degrees, replacing characters with upside-down lookalikes and reversing
+    /// the order of characters and lines.
+    UpsideDown,
+    /// Flip text horizontally, replacing characters with mirrored lookalikes and reversing the
+    /// order of characters in each line.
+    Mirror,
+}
+
+impl Transform {
+    /// All transforms in the order shown to users.
+    pub const ALL: &'static [Transform] = &[Transform::UpsideDown, Transform::Mirror];
+
+    /// Name of the transform used in the command line, e.g. `upside-down`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::UpsideDown => "upside-down",
+            Transform::Mirror => "mirror",
+        }
+    }
+
+    /// Get the character which looks like given one transformed.
+    /// Characters without such a lookalike are returned as they are.
+    pub fn get(&self, c: char) -> char {
+        let pairs = match self {
+            Transform::UpsideDown => UPSIDE_DOWN,
+            Transform::Mirror => MIRROR,
+        };
+        // Transforms are their own inverse, so the pairs are looked up in both directions.
+        pairs
+            .iter()
+            .find_map(|&(a, b)| match c {
+                _ if c == a => Some(b),
+                _ if c == b => Some(a),
+                _ => None,
+            })
+            .unwrap_or(c)
+    }
+}
+
+// Pairs of characters which look like each other rotated by 180 degrees.
+static UPSIDE_DOWN: &[(char, char)] = &[
+    ('a', 'ɐ'),
+    ('b', 'q'),
+    ('c', 'ɔ'),
+    ('d', 'p'),
+    ('e', 'ǝ'),
+    ('f', 'ɟ'),
+    ('g', 'ƃ'),
+    ('h', 'ɥ'),
+    ('i', 'ᴉ'),
+    ('j', 'ɾ'),
+    ('k', 'ʞ'),
+    ('m', 'ɯ'),
+    ('n', 'u'),
+    ('r', 'ɹ'),
+    ('t', 'ʇ'),
+    ('v', 'ʌ'),
+    ('w', 'ʍ'),
+    ('y', 'ʎ'),
+    ('A', '∀'),
+    ('B', 'ꓭ'),
+    ('C', 'Ɔ'),
+    ('D', 'ꓷ'),
+    ('E', 'Ǝ'),
+    ('F', 'Ⅎ'),
+    ('G', '⅁'),
+    ('J', 'ſ'),
+    ('K', 'ꓘ'),
+    ('L', '˥'),
+    ('M', 'W'),
+    ('P', 'Ԁ'),
+    ('Q', 'Ό'),
+    ('R', 'ᴚ'),
+    ('T', '⊥'),
+    ('U', '∩'),
+    ('V', 'Λ'),
+    ('Y', '⅄'),
+    ('1', 'Ɩ'),
+    ('2', '↊'),
+    ('3', '↋'),
+    ('4', 'ㄣ'),
+    ('5', 'ϛ'),
+    ('6', '9'),
+    ('7', 'ㄥ'),
+    ('?', '¿'),
+    ('!', '¡'),
+    ('.', '˙'),
+    (',', '\''),
+    ('"', '„'),
+    (';', '؛'),
+    ('_', '‾'),
+    ('&', '⅋'),
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('<', '>'),
+];
+
+// Pairs of characters which look like each other flipped horizontally.
+static MIRROR: &[(char, char)] = &[
+    ('a', 'ɒ'),
+    ('b', 'd'),
+    ('c', 'ɔ'),
+    ('e', 'ɘ'),
+    ('p', 'q'),
+    ('r', 'ɿ'),
+    ('s', 'ꙅ'),
+    ('t', 'ƚ'),
+    ('z', 'ƹ'),
+    ('ʀ', 'ᴙ'),
+    ('B', 'ᙠ'),
+    ('C', 'Ɔ'),
+    ('D', 'ᗡ'),
+    ('E', 'Ǝ'),
+    ('F', 'ꟻ'),
+    ('K', 'ꓘ'),
+    ('L', '⅃'),
+    ('N', 'И'),
+    ('P', 'ꟼ'),
+    ('R', 'Я'),
+    ('S', 'Ꙅ'),
+    ('Z', 'Ƹ'),
+    ('3', 'Ɛ'),
+    ('?', '⸮'),
+    (';', '⁏'),
+    ('/', '\\'),
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('<', '>'),
+];
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing a string which is not a name of any transform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTransformError(());
+
+impl fmt::Display for ParseTransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown transform name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseTransformError {}
+
+impl FromStr for Transform {
+    type Err = ParseTransformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Transform::ALL
+            .iter()
+            .find(|transform| transform.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(ParseTransformError(()))
+    }
+}
+
+#[cfg(feature = "cli")]
+impl clap::ValueEnum for Transform {
+    fn value_variants<'a>() -> &'a [Self] {
+        Transform::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.name()))
+    }
+}
+
+#[cfg(feature = "transform")]
+impl Converter {
+    /// Transform given characters, keeping grapheme clusters such as a letter with combining
+    /// marks together. Characters decorated with any font are converted to normal ones beforehand.
+    pub fn transform(&self, source: &[char], transform: Transform) -> String {
+        self.transform_str(&source.iter().collect::<String>(), transform)
+    }
+
+    /// Same as `Converter::transform`, but takes a string slice.
+    pub fn transform_str(&self, source: &str, transform: Transform) -> String {
+        let source = Normalize::new(source.chars(), Converter::normalize_char).collect::<String>();
+        let lines = source
+            .split_inclusive('\n')
+            .map(|line| {
+                let content = line.trim_end_matches(['\n', '\r']);
+                (content, &line[content.len()..])
+            })
+            .collect::<Vec<_>>();
+        let mut transformed = String::with_capacity(source.len());
+        // Line endings stay where they are, so that the text keeps ending with a newline or not.
+        for (i, &(_, ending)) in lines.iter().enumerate() {
+            let (content, _) = match transform {
+                Transform::UpsideDown => lines[lines.len() - 1 - i],
+                Transform::Mirror => lines[i],
+            };
+            // Graphemes are reversed as a whole to keep combining marks on their base characters.
+            for grapheme in content.graphemes(true).rev() {
+                // U+200C belongs to the grapheme before it, so it is moved to the front to stay
+                // between the same characters after reversing.
+                let grapheme = match grapheme.strip_suffix('\u{200C}') {
+                    Some(rest) => {
+                        transformed.push('\u{200C}');
+                        rest
+                    }
+                    None => grapheme,
+                };
+                transformed.extend(grapheme.chars().map(|c| transform.get(c)));
+            }
+            transformed.push_str(ending);
+        }
+        transformed
+    }
+}
+
+#[cfg(all(test, feature = "transform"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_name() {
+        for &transform in Transform::ALL {
+            assert_eq!(Ok(transform), transform.to_string().parse());
+        }
+        assert!("upside_down".parse::<Transform>().is_err());
+    }
+
+    #[test]
+    fn upside_down() {
+        let converter = Converter::new();
+        assert_eq!(
+            "¿ǝɯ ɹɐǝɥ noʎ uɐƆ",
+            converter.transform_str("Can you hear me?", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "(ɐ)[q]{ɔ}<p>",
+            converter.transform_str("<d>{c}[b](a)", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "Hello, World!",
+            converter.transform_str(
+                &converter.transform_str("Hello, World!", Transform::UpsideDown),
+                Transform::UpsideDown
+            )
+        );
+    }
+
+    #[test]
+    fn upside_down_lines() {
+        let converter = Converter::new();
+        assert_eq!(
+            "pɔ\r\nqɐ\n",
+            converter.transform_str("ab\r\ncd\n", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "Ɩ\nƖƖ",
+            converter.transform_str("11\n1", Transform::UpsideDown)
+        );
+    }
+
+    #[test]
+    fn upside_down_graphemes() {
+        let converter = Converter::new();
+        assert_eq!(
+            "ǝ\u{301}ɟɐɔ",
+            converter.transform_str("cafe\u{301}", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "q\u{200C}ɐ",
+            converter.transform_str("a\u{200C}b", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "qɐ",
+            converter.transform_str("🇦\u{200C}🇧", Transform::UpsideDown)
+        );
+        assert_eq!(
+            "🇯🇵 ǝ",
+            converter.transform_str("e 🇯🇵", Transform::UpsideDown)
+        );
+    }
+
+    #[test]
+    fn mirror() {
+        let converter = Converter::new();
+        assert_eq!(
+            "ƚɿɒƚꙅ ɘhƚ oƚ kɔɒᙠ",
+            converter.transform_str("Back to the start", Transform::Mirror)
+        );
+        assert_eq!("ᴙ", converter.transform_str("ʀ", Transform::Mirror));
+        assert_eq!(
+            "ɔdɒ\nᗡƆᙠ\n",
+            converter.transform_str("abc\nBCD\n", Transform::Mirror)
+        );
+        assert_eq!(
+            "\u{1F44D}\u{1F3FD} ɘ\u{301}",
+            converter.transform_str("e\u{301} \u{1F44D}\u{1F3FD}", Transform::Mirror)
+        );
+    }
+
+    #[test]
+    fn upside_down_decorated() {
+        let converter = Converter::new();
+        assert_eq!("¡ᴉɥ", converter.transform_str("𝐡𝐢!", Transform::UpsideDown));
+    }
+}